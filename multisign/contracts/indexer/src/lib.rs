@@ -0,0 +1,143 @@
+#![no_std]
+
+//! Companion read model for a multisig wallet. Registered as the wallet's
+//! proposal listener, it keeps proposal ids indexed by status, by recipient
+//! and by tag (the proposal's `data` memo), so dashboards can query what the
+//! wallet itself does not store in a searchable form.
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, BytesN, Env, IntoVal, Symbol, Val, Vec,
+};
+
+mod test;
+
+const WALLET_KEY: Symbol = symbol_short!("wallet");
+const ENTRY_KEY: Symbol = symbol_short!("entry");
+const STATUS_KEY: Symbol = symbol_short!("status");
+const RECIPIENT_KEY: Symbol = symbol_short!("to");
+const TAG_KEY: Symbol = symbol_short!("tag");
+
+/// Mirrors the wallet's `TransactionStatus`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TransactionStatus {
+    Draft,
+    Pending,
+    Executed,
+    Cancelled,
+    Expired,
+}
+
+/// Mirrors the wallet's `ProposalUpdate`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalUpdate {
+    pub transaction_id: u32,
+    pub status: TransactionStatus,
+    pub submitter: Address,
+    pub to: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub data: BytesN<32>,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum IndexerError {
+    UnknownWallet = 1,
+}
+
+#[contract]
+pub struct ProposalIndexer;
+
+impl ProposalIndexer {
+    fn ids<K: IntoVal<Env, Val>>(env: &Env, key: &K) -> Vec<u32> {
+        env.storage().persistent().get(key).unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn index_add<K: IntoVal<Env, Val>>(env: &Env, key: &K, id: u32) {
+        let mut ids = Self::ids(env, key);
+        if !ids.contains(id) {
+            ids.push_back(id);
+            env.storage().persistent().set(key, &ids);
+        }
+    }
+
+    fn index_remove<K: IntoVal<Env, Val>>(env: &Env, key: &K, id: u32) {
+        let mut ids = Self::ids(env, key);
+        if let Some(index) = ids.first_index_of(id) {
+            ids.remove(index);
+            if ids.is_empty() {
+                env.storage().persistent().remove(key);
+            } else {
+                env.storage().persistent().set(key, &ids);
+            }
+        }
+    }
+}
+
+#[contractimpl]
+impl ProposalIndexer {
+    /// Binds the indexer to the one wallet whose updates it accepts.
+    pub fn __constructor(env: Env, wallet: Address) {
+        env.storage().instance().set(&WALLET_KEY, &wallet);
+    }
+
+    /// Listener entry point. Re-files the proposal under whichever of its
+    /// status, recipient and tag changed.
+    pub fn on_proposal(env: Env, wallet: Address, update: ProposalUpdate) -> Result<(), IndexerError> {
+        if Some(&wallet) != env.storage().instance().get::<_, Address>(&WALLET_KEY).as_ref() {
+            return Err(IndexerError::UnknownWallet);
+        }
+        wallet.require_auth();
+
+        let id = update.transaction_id;
+        let entry_key = (ENTRY_KEY, id);
+        let previous: Option<ProposalUpdate> = env.storage().persistent().get(&entry_key);
+
+        if previous.as_ref().map(|previous| previous.status) != Some(update.status) {
+            if let Some(previous) = &previous {
+                Self::index_remove(&env, &(STATUS_KEY, previous.status), id);
+            }
+            Self::index_add(&env, &(STATUS_KEY, update.status), id);
+        }
+        if previous.as_ref().map(|previous| &previous.to) != Some(&update.to) {
+            if let Some(previous) = &previous {
+                Self::index_remove(&env, &(RECIPIENT_KEY, previous.to.clone()), id);
+            }
+            Self::index_add(&env, &(RECIPIENT_KEY, update.to.clone()), id);
+        }
+        if previous.as_ref().map(|previous| &previous.data) != Some(&update.data) {
+            if let Some(previous) = &previous {
+                Self::index_remove(&env, &(TAG_KEY, previous.data.clone()), id);
+            }
+            Self::index_add(&env, &(TAG_KEY, update.data.clone()), id);
+        }
+        env.storage().persistent().set(&entry_key, &update);
+
+        Ok(())
+    }
+
+    pub fn get_wallet(env: Env) -> Address {
+        env.storage().instance().get(&WALLET_KEY).unwrap()
+    }
+
+    /// The latest update seen for `transaction_id`.
+    pub fn get_entry(env: Env, transaction_id: u32) -> Option<ProposalUpdate> {
+        env.storage().persistent().get(&(ENTRY_KEY, transaction_id))
+    }
+
+    /// Ids of proposals currently in `status`, in the order they reached it.
+    pub fn by_status(env: Env, status: TransactionStatus) -> Vec<u32> {
+        Self::ids(&env, &(STATUS_KEY, status))
+    }
+
+    pub fn by_recipient(env: Env, to: Address) -> Vec<u32> {
+        Self::ids(&env, &(RECIPIENT_KEY, to))
+    }
+
+    pub fn by_tag(env: Env, tag: BytesN<32>) -> Vec<u32> {
+        Self::ids(&env, &(TAG_KEY, tag))
+    }
+}
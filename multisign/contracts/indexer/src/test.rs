@@ -0,0 +1,71 @@
+#![cfg(test)]
+
+use super::*;
+use multi_sign::{ConfigChange, MultisigContract, MultisigContractClient};
+use soroban_sdk::{testutils::Address as _, token, vec, Address, BytesN, Env};
+
+#[test]
+fn test_indexes_follow_wallet_proposals() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let wallet_id = env.register(MultisigContract, (None::<Address>,));
+    let wallet = MultisigContractClient::new(&env, &wallet_id);
+    let indexer_id = env.register(ProposalIndexer, (wallet_id.clone(),));
+    let indexer = ProposalIndexerClient::new(&env, &indexer_id);
+    
+    let owner = Address::generate(&env);
+    wallet.initialize(&vec![&env, owner.clone()], &1);
+    let listen_id = wallet.submit_config_change(&owner, &ConfigChange::SetListener(Some(indexer_id.clone())));
+    wallet.execute_transaction(&owner, &listen_id);
+    assert_eq!(wallet.get_listener(), Some(indexer_id.clone()));
+    
+    let sac = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    token::StellarAssetClient::new(&env, &sac.address()).mint(&wallet_id, &1000);
+    
+    let to = Address::generate(&env);
+    let invoice = BytesN::from_array(&env, &[7; 32]);
+    let paid = wallet.submit_transaction(&owner, &to, &100, &sac.address(), &invoice);
+    let open = wallet.submit_transaction(&owner, &to, &200, &sac.address(), &BytesN::from_array(&env, &[8; 32]));
+    assert_eq!(indexer.by_status(&TransactionStatus::Pending), vec![&env, paid, open]);
+    
+    wallet.execute_transaction(&owner, &paid);
+    assert_eq!(indexer.by_status(&TransactionStatus::Pending), vec![&env, open]);
+    assert_eq!(indexer.by_status(&TransactionStatus::Executed), vec![&env, listen_id, paid]);
+    assert_eq!(indexer.by_recipient(&to), vec![&env, paid, open]);
+    assert_eq!(indexer.by_tag(&invoice), vec![&env, paid]);
+    assert_eq!(indexer.get_entry(&paid).unwrap().amount, 100);
+    
+    // Drafts are re-filed when amended
+    let other = Address::generate(&env);
+    let draft = wallet.submit_draft(&owner, &to, &5, &sac.address(), &invoice);
+    wallet.amend_draft(&owner, &draft, &other, &5, &invoice);
+    assert_eq!(indexer.by_recipient(&to), vec![&env, paid, open]);
+    assert_eq!(indexer.by_recipient(&other), vec![&env, draft]);
+    assert_eq!(indexer.by_status(&TransactionStatus::Draft), vec![&env, draft]);
+}
+
+#[test]
+fn test_rejects_updates_from_other_wallets() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let wallet = Address::generate(&env);
+    let indexer_id = env.register(ProposalIndexer, (wallet.clone(),));
+    let indexer = ProposalIndexerClient::new(&env, &indexer_id);
+    
+    let update = ProposalUpdate {
+        transaction_id: 1,
+        status: TransactionStatus::Pending,
+        submitter: Address::generate(&env),
+        to: Address::generate(&env),
+        token: Address::generate(&env),
+        amount: 1,
+        data: BytesN::from_array(&env, &[0; 32]),
+    };
+    let result = indexer.try_on_proposal(&Address::generate(&env), &update);
+    assert_eq!(result, Err(Ok(IndexerError::UnknownWallet)));
+    
+    indexer.on_proposal(&wallet, &update);
+    assert_eq!(indexer.by_status(&TransactionStatus::Pending), vec![&env, 1]);
+}
@@ -0,0 +1,141 @@
+//! Token allowances granted by the wallet. Every grant is remembered with its
+//! expiry so that a single `RevokeAllAllowances` proposal can zero them all,
+//! e.g. after a protocol the wallet has approved is compromised, and so that
+//! lapsed grants can be swept by anyone.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, token, Address, BytesN, Env, Symbol, Vec};
+
+use crate::{MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError, TransactionKind};
+
+pub(crate) const ALLOWANCES_KEY: Symbol = symbol_short!("allowance");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowanceGrant {
+    pub token: Address,
+    pub spender: Address,
+    pub amount: i128,
+    /// Ledger after which the token contract no longer honours the allowance.
+    pub expiration_ledger: u32,
+}
+
+impl MultisigContract {
+    fn granted_allowances(env: &Env) -> Vec<AllowanceGrant> {
+        env.storage().persistent().get(&ALLOWANCES_KEY).unwrap_or_else(|| Vec::new(env))
+    }
+
+    pub(crate) fn execute_approve(env: &Env, grant: AllowanceGrant) -> Result<(), MultisigError> {
+        Self::ensure_token_not_paused(env, &grant.token)?;
+
+        token::Client::new(env, &grant.token).approve(
+            &env.current_contract_address(),
+            &grant.spender,
+            &grant.amount,
+            &grant.expiration_ledger,
+        );
+
+        // A new grant to the same spender replaces the old allowance.
+        let mut grants = Self::granted_allowances(env);
+        if let Some(index) = grants.iter()
+            .position(|existing| existing.token == grant.token && existing.spender == grant.spender)
+        {
+            grants.remove(index as u32);
+        }
+        grants.push_back(grant.clone());
+        env.storage().persistent().set(&ALLOWANCES_KEY, &grants);
+
+        Self::publish_event(env, (symbol_short!("approve"), grant.token), (grant.spender, grant.amount));
+
+        Ok(())
+    }
+
+    /// Zeroes every recorded allowance and forgets them.
+    pub(crate) fn revoke_all_allowances(env: &Env) -> Result<(), MultisigError> {
+        let this = env.current_contract_address();
+        let grants = Self::granted_allowances(env);
+        for grant in grants.iter() {
+            token::Client::new(env, &grant.token).approve(&this, &grant.spender, &0, &env.ledger().sequence());
+        }
+        env.storage().persistent().remove(&ALLOWANCES_KEY);
+
+        Self::publish_event(env, (symbol_short!("revoke"),), grants.len());
+
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Proposes letting `grant.spender` pull up to `grant.amount` of the
+    /// wallet's `grant.token`.
+    pub fn submit_approve(
+        env: Env,
+        caller: Address,
+        grant: AllowanceGrant,
+    ) -> Result<u32, MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        if grant.amount <= 0 {
+            return Err(MultisigError::InvalidAmount);
+        }
+        if Self::is_unusable_address(&env, &grant.token) {
+            return Err(MultisigError::InvalidToken);
+        }
+        if Self::is_unusable_address(&env, &grant.spender) {
+            return Err(MultisigError::InvalidAddress);
+        }
+        if grant.expiration_ledger < env.ledger().sequence() {
+            return Err(MultisigError::InvalidSchedule);
+        }
+
+        let data = BytesN::from_array(&env, &[0; 32]);
+        Self::store_transaction(
+            &env,
+            &caller,
+            grant.spender.clone(),
+            grant.amount,
+            grant.token.clone(),
+            data,
+            TransactionKind::Approve(grant),
+        )
+    }
+
+    /// Grants that are still live, in the order they were made.
+    pub fn get_outstanding_allowances(env: Env) -> Vec<AllowanceGrant> {
+        let sequence = env.ledger().sequence();
+        let mut outstanding = Vec::new(&env);
+        for grant in Self::granted_allowances(&env).iter() {
+            if grant.expiration_ledger >= sequence {
+                outstanding.push_back(grant);
+            }
+        }
+        outstanding
+    }
+
+    /// Permissionless sweep that zeroes and forgets every grant past its
+    /// expiration ledger. Returns how many were removed.
+    pub fn expire_allowances(env: Env) -> u32 {
+        let this = env.current_contract_address();
+        let sequence = env.ledger().sequence();
+        let grants = Self::granted_allowances(&env);
+
+        let mut kept = Vec::new(&env);
+        let mut expired = 0;
+        for grant in grants.iter() {
+            if grant.expiration_ledger >= sequence {
+                kept.push_back(grant);
+                continue;
+            }
+            token::Client::new(&env, &grant.token).approve(&this, &grant.spender, &0, &sequence);
+            Self::publish_event(&env, (symbol_short!("allow_exp"), grant.token), grant.spender);
+            expired += 1;
+        }
+
+        if expired > 0 {
+            env.storage().persistent().set(&ALLOWANCES_KEY, &kept);
+        }
+
+        expired
+    }
+}
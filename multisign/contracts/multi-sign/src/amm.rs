@@ -0,0 +1,161 @@
+//! Liquidity proposals against Uniswap-V2 style pair contracts (e.g. Soroswap
+//! pairs), where tokens are sent to the pair before `deposit`/`withdraw` and
+//! the pair contract is itself the LP token.
+
+use soroban_sdk::{
+    contractclient, contractimpl, contracttype, symbol_short, token, Address, BytesN, Env, Symbol, Vec,
+};
+
+use crate::{MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError, TransactionKind};
+
+pub(crate) const TRACKED_ASSETS_KEY: Symbol = symbol_short!("assets");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AddLiquidity {
+    pub pool: Address,
+    /// Amount of the pool's `token_0` to deposit.
+    pub amount_0: i128,
+    /// Amount of the pool's `token_1` to deposit.
+    pub amount_1: i128,
+    pub min_liquidity: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RemoveLiquidity {
+    pub pool: Address,
+    pub liquidity: i128,
+    pub min_amount_0: i128,
+    pub min_amount_1: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LiquidityAction {
+    Add(AddLiquidity),
+    Remove(RemoveLiquidity),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetBalance {
+    pub token: Address,
+    pub balance: i128,
+}
+
+#[contractclient(name = "LiquidityPoolClient")]
+pub trait LiquidityPool {
+    fn token_0(env: Env) -> Address;
+    fn token_1(env: Env) -> Address;
+    /// Mints LP tokens to `to` for the tokens sent to the pool beforehand.
+    fn deposit(env: Env, to: Address) -> i128;
+    /// Burns the LP tokens sent to the pool beforehand, paying both tokens to `to`.
+    fn withdraw(env: Env, to: Address) -> (i128, i128);
+}
+
+impl MultisigContract {
+    /// Adds `token` to the assets reported by `get_balances`.
+    pub(crate) fn track_asset(env: &Env, token: &Address) {
+        let mut assets: Vec<Address> = env.storage().persistent().get(&TRACKED_ASSETS_KEY)
+            .unwrap_or_else(|| Vec::new(env));
+        if !assets.contains(token) {
+            assets.push_back(token.clone());
+            env.storage().persistent().set(&TRACKED_ASSETS_KEY, &assets);
+        }
+    }
+
+    pub(crate) fn execute_liquidity(env: &Env, action: LiquidityAction) -> Result<(), MultisigError> {
+        let this = env.current_contract_address();
+        match action {
+            LiquidityAction::Add(add) => {
+                let pool = LiquidityPoolClient::new(env, &add.pool);
+                let token_0 = pool.token_0();
+                let token_1 = pool.token_1();
+                Self::check_savings_lock(env, &token_0, add.amount_0, false)?;
+                Self::check_savings_lock(env, &token_1, add.amount_1, false)?;
+                Self::check_reserved_balance(env, &token_0, add.amount_0)?;
+                Self::check_reserved_balance(env, &token_1, add.amount_1)?;
+                Self::ensure_token_not_paused(env, &token_0)?;
+                Self::ensure_token_not_paused(env, &token_1)?;
+
+                token::Client::new(env, &token_0).transfer(&this, &add.pool, &add.amount_0);
+                token::Client::new(env, &token_1).transfer(&this, &add.pool, &add.amount_1);
+                let minted = pool.deposit(&this);
+                if minted < add.min_liquidity {
+                    return Err(MultisigError::InsufficientLiquidity);
+                }
+
+                Self::track_asset(env, &token_0);
+                Self::track_asset(env, &token_1);
+                Self::track_asset(env, &add.pool);
+
+                Self::publish_event(
+                    env,
+                    (symbol_short!("lp_add"), add.pool),
+                    (add.amount_0, add.amount_1, minted)
+                );
+            },
+            LiquidityAction::Remove(remove) => {
+                token::Client::new(env, &remove.pool).transfer(&this, &remove.pool, &remove.liquidity);
+                let (amount_0, amount_1) = LiquidityPoolClient::new(env, &remove.pool).withdraw(&this);
+                if amount_0 < remove.min_amount_0 || amount_1 < remove.min_amount_1 {
+                    return Err(MultisigError::InsufficientLiquidity);
+                }
+
+                Self::publish_event(
+                    env,
+                    (symbol_short!("lp_remove"), remove.pool),
+                    (remove.liquidity, amount_0, amount_1)
+                );
+            },
+        }
+
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    pub fn submit_liquidity(
+        env: Env,
+        caller: Address,
+        action: LiquidityAction,
+    ) -> Result<u32, MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        let pool = match &action {
+            LiquidityAction::Add(add) => {
+                if add.amount_0 <= 0 || add.amount_1 <= 0 || add.min_liquidity < 0 {
+                    return Err(MultisigError::InvalidAmount);
+                }
+                add.pool.clone()
+            },
+            LiquidityAction::Remove(remove) => {
+                if remove.liquidity <= 0 || remove.min_amount_0 < 0 || remove.min_amount_1 < 0 {
+                    return Err(MultisigError::InvalidAmount);
+                }
+                remove.pool.clone()
+            },
+        };
+
+        let data = BytesN::from_array(&env, &[0; 32]);
+        Self::store_transaction(&env, &caller, pool.clone(), 0, pool, data, TransactionKind::Liquidity(action))
+    }
+
+    /// Current wallet balance of every tracked asset, LP tokens included.
+    pub fn get_balances(env: Env) -> Vec<AssetBalance> {
+        let this = env.current_contract_address();
+        let assets: Vec<Address> = env.storage().persistent().get(&TRACKED_ASSETS_KEY)
+            .unwrap_or_else(|| Vec::new(&env));
+
+        let mut balances = Vec::new(&env);
+        for token in assets.iter() {
+            let balance = token::Client::new(&env, &token).balance(&this);
+            balances.push_back(AssetBalance { token, balance });
+        }
+
+        balances
+    }
+}
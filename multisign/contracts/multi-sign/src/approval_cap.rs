@@ -0,0 +1,88 @@
+//! Conditional approvals. An owner may agree to a proposal only up to an
+//! amount: the approval is recorded on-chain with its cap, and counts
+//! towards quorum only while the amount the proposal moves, summed over a
+//! batch's legs, is within it. Caps stop counting approvals, never add to
+//! them.
+
+use soroban_sdk::{contractimpl, symbol_short, Address, Env, Map, Symbol};
+
+use crate::{MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError, Transaction};
+
+pub(crate) const APPROVAL_CAP_KEY: Symbol = symbol_short!("appr_cap");
+
+impl MultisigContract {
+    fn approval_caps(env: &Env, transaction_id: u32) -> Map<Address, i128> {
+        env.storage().persistent().get(&(APPROVAL_CAP_KEY, transaction_id)).unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Whether `owner`'s approval, if given, counts for this proposal.
+    pub(crate) fn approval_condition_met(env: &Env, transaction_id: u32, transaction: &Transaction, owner: &Address) -> bool {
+        Self::approval_caps(env, transaction_id).get(owner.clone())
+            .is_none_or(|cap| Self::moved_amount(env, transaction) <= cap)
+    }
+
+    /// Owner approval weight on the proposal, leaving out approvals whose
+    /// cap the moved amount exceeds.
+    pub(crate) fn owner_approvals(env: &Env, transaction_id: u32, transaction: &Transaction) -> u32 {
+        let caps = Self::approval_caps(env, transaction_id);
+        if caps.is_empty() {
+            return transaction.approvals;
+        }
+        let Ok(config) = Self::get_config(env) else {
+            return transaction.approvals;
+        };
+        let amount = Self::moved_amount(env, transaction);
+        let unmet = caps.iter()
+            .filter(|(owner, cap)| amount > *cap && Self::has_approved(env, transaction_id, owner))
+            .map(|(owner, _)| Self::owner_weight(&config, &owner))
+            .fold(0u32, u32::saturating_add);
+        transaction.approvals.saturating_sub(unmet)
+    }
+
+    pub(crate) fn clear_approval_cap(env: &Env, transaction_id: u32, owner: &Address) {
+        let mut caps = Self::approval_caps(env, transaction_id);
+        if caps.contains_key(owner.clone()) {
+            caps.remove(owner.clone());
+            env.storage().persistent().set(&(APPROVAL_CAP_KEY, transaction_id), &caps);
+        }
+    }
+
+    pub(crate) fn clear_approval_caps(env: &Env, transaction_id: u32) {
+        env.storage().persistent().remove(&(APPROVAL_CAP_KEY, transaction_id));
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Approves the proposal on condition that it moves at most
+    /// `max_amount`. The approval is shown to the other owners either way,
+    /// but counts only while the condition holds.
+    pub fn approve_with_cap(
+        env: Env,
+        caller: Address,
+        transaction_id: u32,
+        max_amount: i128,
+    ) -> Result<(), MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+        if max_amount <= 0 {
+            return Err(MultisigError::InvalidAmount);
+        }
+
+        // The cap is in place before the approval is tallied, so a condition
+        // that does not hold never counts towards quorum.
+        let mut caps = Self::approval_caps(&env, transaction_id);
+        caps.set(caller.clone(), max_amount);
+        env.storage().persistent().set(&(APPROVAL_CAP_KEY, transaction_id), &caps);
+        Self::record_approval(&env, &caller, transaction_id)?;
+
+        Self::publish_event(&env, (symbol_short!("appr_cap"), transaction_id), (caller, max_amount));
+
+        Ok(())
+    }
+
+    /// The cap `owner` attached to their approval, if it was conditional.
+    pub fn get_approval_cap(env: Env, transaction_id: u32, owner: Address) -> Option<i128> {
+        Self::approval_caps(&env, transaction_id).get(owner)
+    }
+}
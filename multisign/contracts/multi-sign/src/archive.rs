@@ -0,0 +1,104 @@
+//! Archival of closed proposals. Executed, cancelled and expired proposals
+//! otherwise stay in persistent storage for good, paying rent. Once the
+//! retention policy lets its votes go, an owner can swap one for a compact
+//! record: a hash of the full proposal and a summary of what it did. Pending
+//! proposals, and any proposal under `KeepForever`, are never archived.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, BytesN, Env, Symbol, Vec};
+
+use crate::{
+    MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError, Transaction, TransactionKind,
+    TransactionStatus, CONTENT_KEY, TX_KEY,
+};
+
+pub(crate) const ARCHIVE_KEY: Symbol = symbol_short!("tx_arch");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArchivedTransaction {
+    /// SHA-256 of the XDR-encoded transaction as it was archived, votes
+    /// already compressed into its `votes_digest`.
+    pub hash: BytesN<32>,
+    pub to: Address,
+    pub amount: i128,
+    pub token: Address,
+    pub submitter: Address,
+    pub status: TransactionStatus,
+    pub closed_at: u32,
+}
+
+impl MultisigContract {
+    pub(crate) fn is_archived(env: &Env, transaction_id: u32) -> bool {
+        env.storage().persistent().has(&(ARCHIVE_KEY, transaction_id))
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Archives every proposal in `transaction_ids` the retention policy no
+    /// longer keeps in full. Ids that are unknown, pending or still retained
+    /// are skipped. Returns how many were archived.
+    pub fn archive_transactions(
+        env: Env,
+        caller: Address,
+        transaction_ids: Vec<u32>,
+    ) -> Result<u32, MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        let config = Self::get_config(&env)?;
+        let mut archived = 0;
+        for transaction_id in transaction_ids.iter() {
+            let tx_key = (TX_KEY, transaction_id);
+            let Some(mut transaction) = env.storage().persistent().get::<_, Transaction>(&tx_key) else {
+                continue;
+            };
+            let Some(closed_at) = transaction.closed_at else {
+                continue;
+            };
+            if !Self::retention_expired(&env, &config, transaction_id, &transaction) {
+                continue;
+            }
+
+            if transaction.votes_digest.is_none() {
+                Self::compress_votes(&env, &config, transaction_id, &mut transaction);
+            }
+            if transaction.kind == TransactionKind::Transfer {
+                let content_key = (CONTENT_KEY, Self::content_hash(
+                    &env, &transaction.to, transaction.amount, &transaction.token, &transaction.data,
+                ));
+                if env.storage().persistent().get::<_, u32>(&content_key) == Some(transaction_id) {
+                    env.storage().persistent().remove(&content_key);
+                }
+            }
+
+            let record = ArchivedTransaction {
+                hash: env.crypto().sha256(&transaction.clone().to_xdr(&env)).into(),
+                to: transaction.to,
+                amount: transaction.amount,
+                token: transaction.token,
+                submitter: transaction.submitter,
+                status: transaction.status,
+                closed_at,
+            };
+            env.storage().persistent().remove(&tx_key);
+            env.storage().persistent().set(&(ARCHIVE_KEY, transaction_id), &record);
+            Self::publish_event(&env, (symbol_short!("archive"), transaction_id), record.hash);
+            archived += 1;
+        }
+
+        Ok(archived)
+    }
+
+    /// The compact record left by archiving a proposal.
+    pub fn get_archived_transaction(
+        env: Env,
+        caller: Address,
+        transaction_id: u32,
+    ) -> Result<ArchivedTransaction, MultisigError> {
+        caller.require_auth();
+        Self::verify_viewer(&env, &caller)?;
+        env.storage().persistent().get(&(ARCHIVE_KEY, transaction_id))
+            .ok_or(MultisigError::TransactionNotFound)
+    }
+}
@@ -0,0 +1,64 @@
+//! Break-glass viewer keys for auditors. Detail views such as proposals,
+//! their votes and rejection reasons answer only to owners. The quorum may
+//! grant an outside auditor read access to them until a given ledger,
+//! without a seat, weight or any say in proposals. Every read an auditor
+//! makes is published, so the access leaves its own trail.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, Env, Symbol};
+
+use crate::{MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError};
+
+const AUDITOR_KEY: Symbol = symbol_short!("auditor");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuditorGrant {
+    /// Last ledger on which the auditor may read.
+    pub expires_at: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AuditorAccess {
+    Revoked,
+    Granted(AuditorGrant),
+}
+
+impl MultisigContract {
+    pub(crate) fn set_auditor(env: &Env, auditor: Address, access: AuditorAccess) -> Result<(), MultisigError> {
+        let key = (AUDITOR_KEY, auditor.clone());
+        match &access {
+            AuditorAccess::Granted(grant) if grant.expires_at <= env.ledger().sequence() => {
+                return Err(MultisigError::InvalidSchedule);
+            },
+            AuditorAccess::Granted(grant) => env.storage().persistent().set(&key, grant),
+            AuditorAccess::Revoked => env.storage().persistent().remove(&key),
+        }
+        Self::publish_event(env, (symbol_short!("auditor"), auditor), access);
+
+        Ok(())
+    }
+
+    /// Lets owners and auditors with a current grant through to detail
+    /// views, and rejects everyone else with `Unauthorized`.
+    pub(crate) fn verify_viewer(env: &Env, caller: &Address) -> Result<(), MultisigError> {
+        if Self::ensure_owner(env, caller).is_ok() {
+            return Ok(());
+        }
+        match Self::get_auditor(env.clone(), caller.clone()) {
+            Some(grant) if env.ledger().sequence() <= grant.expires_at => {
+                Self::publish_event(env, (symbol_short!("audit_rd"), caller.clone()), env.ledger().sequence());
+                Ok(())
+            },
+            _ => Err(MultisigError::Unauthorized),
+        }
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// The auditor's grant, including one that has run out but was not revoked.
+    pub fn get_auditor(env: Env, auditor: Address) -> Option<AuditorGrant> {
+        env.storage().persistent().get(&(AUDITOR_KEY, auditor))
+    }
+}
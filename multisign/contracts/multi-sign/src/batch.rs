@@ -0,0 +1,85 @@
+//! Batch proposals. A batch carries up to 20 operations, such as a payroll
+//! run, that are approved once and executed in order by a single
+//! `execute_transaction`. If any operation fails the whole batch fails and
+//! stays pending, so it never half-runs.
+
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Address, BytesN, Env, Vec};
+
+use crate::{
+    ContractCall, ExtendedError, MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError, Transaction,
+    TransactionKind,
+};
+
+const MAX_BATCH_LEN: u32 = 20;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenTransfer {
+    pub to: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Operation {
+    TokenTransfer(TokenTransfer),
+    ContractCall(ContractCall),
+}
+
+impl MultisigContract {
+    /// The batch's operations as legs of `transaction`, each carrying its own
+    /// recipient, token and amount so transfer checks apply per payment.
+    pub(crate) fn batch_legs(env: &Env, transaction: &Transaction, operations: Vec<Operation>) -> Vec<Transaction> {
+        let mut legs = Vec::new(env);
+        for operation in operations.iter() {
+            let mut leg = transaction.clone();
+            match operation {
+                Operation::TokenTransfer(transfer) => {
+                    leg.to = transfer.to;
+                    leg.token = transfer.token;
+                    leg.amount = transfer.amount;
+                    leg.kind = TransactionKind::Transfer;
+                },
+                Operation::ContractCall(call) => {
+                    leg.to = call.contract.clone();
+                    leg.token = call.contract.clone();
+                    leg.amount = 0;
+                    leg.kind = TransactionKind::ContractCall(call);
+                },
+            }
+            legs.push_back(leg);
+        }
+        legs
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Proposes running `operations` in order as one all-or-nothing action.
+    /// It needs the highest threshold any of its tokens calls for.
+    pub fn submit_batch(
+        env: Env,
+        caller: Address,
+        operations: Vec<Operation>,
+    ) -> Result<u32, MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        if operations.is_empty() || operations.len() > MAX_BATCH_LEN {
+            panic_with_error!(&env, ExtendedError::InvalidBatch);
+        }
+        for operation in operations.iter() {
+            match operation {
+                Operation::TokenTransfer(transfer) => {
+                    Self::validate_transaction_inputs(&env, &transfer.to, transfer.amount, &transfer.token)?
+                },
+                Operation::ContractCall(call) => Self::validate_contract_call(&env, &call)?,
+            }
+        }
+
+        let this = env.current_contract_address();
+        let data = BytesN::from_array(&env, &[0; 32]);
+        Self::store_transaction(&env, &caller, this.clone(), 0, this, data, TransactionKind::Batch(operations))
+    }
+}
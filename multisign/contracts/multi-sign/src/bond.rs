@@ -0,0 +1,168 @@
+//! Anti-spam bonds: while a bond is required, every submitter locks it in the
+//! wallet. It is returned when the proposal is executed, withdrawn or expires,
+//! and kept by the wallet once a rejection quorum stands against it, however
+//! the proposal is then cancelled.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, token, Address, Env, Symbol};
+
+use crate::{
+    MultisigConfig, MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError,
+    Transaction, TransactionStatus, TX_KEY,
+};
+
+pub(crate) const BOND_KEY: Symbol = symbol_short!("bond");
+const BOND_HELD_KEY: Symbol = symbol_short!("bond_held");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalBond {
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BondRequirement {
+    Disabled,
+    Required(ProposalBond),
+}
+
+impl MultisigContract {
+    pub(crate) fn validate_bond_requirement(
+        env: &Env,
+        requirement: &BondRequirement,
+    ) -> Result<(), MultisigError> {
+        if let BondRequirement::Required(bond) = requirement {
+            if bond.amount <= 0 {
+                return Err(MultisigError::InvalidAmount);
+            }
+            if Self::is_unusable_address(env, &bond.token) {
+                return Err(MultisigError::InvalidToken);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Takes the configured bond from the submitter of a new proposal.
+    pub(crate) fn lock_bond(
+        env: &Env,
+        config: &MultisigConfig,
+        submitter: &Address,
+        transaction_id: u32,
+    ) -> Result<(), MultisigError> {
+        let BondRequirement::Required(bond) = &config.bond else {
+            return Ok(());
+        };
+
+        token::Client::new(env, &bond.token)
+            .transfer(submitter, &env.current_contract_address(), &bond.amount);
+
+        let held = Self::get_bonds_held(env.clone(), bond.token.clone())
+            .checked_add(bond.amount)
+            .ok_or(MultisigError::ArithmeticError)?;
+        env.storage().persistent().set(&(BOND_HELD_KEY, bond.token.clone()), &held);
+        env.storage().persistent().set(&(BOND_KEY, transaction_id), bond);
+
+        Ok(())
+    }
+
+    /// Returns the bond of a closed proposal to its submitter, or forfeits it to
+    /// the wallet. Proposals submitted without a bond are left alone.
+    pub(crate) fn settle_bond(
+        env: &Env,
+        transaction_id: u32,
+        transaction: &Transaction,
+        refund: bool,
+    ) -> Result<(), MultisigError> {
+        let key = (BOND_KEY, transaction_id);
+        let Some(bond) = env.storage().persistent().get::<_, ProposalBond>(&key) else {
+            return Ok(());
+        };
+        env.storage().persistent().remove(&key);
+
+        let held = Self::get_bonds_held(env.clone(), bond.token.clone()) - bond.amount;
+        env.storage().persistent().set(&(BOND_HELD_KEY, bond.token.clone()), &held);
+
+        if refund {
+            token::Client::new(env, &bond.token)
+                .transfer(&env.current_contract_address(), &transaction.submitter, &bond.amount);
+            Self::publish_event(env, (symbol_short!("bond_ret"), transaction_id), bond.amount);
+        } else {
+            Self::publish_event(env, (symbol_short!("bond_lost"), transaction_id), bond.amount);
+        }
+
+        Ok(())
+    }
+
+    /// Keeps outgoing transfers from spending bonds that are owed back to
+    /// submitters, tokens set aside for unclaimed payments, or the fee pool.
+    pub(crate) fn check_reserved_balance(
+        env: &Env,
+        token: &Address,
+        amount: i128,
+    ) -> Result<(), MultisigError> {
+        let held = Self::get_bonds_held(env.clone(), token.clone())
+            + Self::get_claims_held(env.clone(), token.clone())
+            + Self::get_fee_pool(env.clone(), token.clone());
+        if held == 0 {
+            return Ok(());
+        }
+
+        let balance = token::Client::new(env, token).balance(&env.current_contract_address());
+        if amount > balance - held {
+            return Err(MultisigError::BondReserved);
+        }
+
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Cancels a proposal that reached the cancel threshold of rejections and
+    /// forfeits its submitter's bond.
+    pub fn cancel_as_spam(
+        env: Env,
+        caller: Address,
+        transaction_id: u32,
+    ) -> Result<(), MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        let tx_key = (TX_KEY, transaction_id);
+        let mut transaction: Transaction = env.storage().persistent().get(&tx_key)
+            .ok_or(MultisigError::TransactionNotFound)?;
+
+        Self::ensure_pending(&env, &transaction)?;
+
+        let config = Self::get_config(&env)?;
+        if transaction.rejections < config.cancel_threshold {
+            return Err(MultisigError::InsufficientRejections);
+        }
+
+        Self::close_pending(&env, &config, transaction_id, &mut transaction, TransactionStatus::Cancelled, false)?;
+
+        Self::publish_event(
+            &env,
+            (symbol_short!("spam"), transaction_id),
+            (caller, transaction.rejections)
+        );
+
+        Ok(())
+    }
+
+    pub fn get_bond_requirement(env: Env) -> Result<BondRequirement, MultisigError> {
+        Ok(Self::get_config(&env)?.bond)
+    }
+
+    /// Bond locked by the submitter of a proposal that is still open.
+    pub fn get_bond(env: Env, transaction_id: u32) -> Option<ProposalBond> {
+        env.storage().persistent().get(&(BOND_KEY, transaction_id))
+    }
+
+    /// Total of a token held in open bonds.
+    pub fn get_bonds_held(env: Env, token: Address) -> i128 {
+        env.storage().persistent().get(&(BOND_HELD_KEY, token)).unwrap_or(0)
+    }
+}
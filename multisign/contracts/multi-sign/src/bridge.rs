@@ -0,0 +1,74 @@
+//! Cross-chain message proposals relayed through the configured bridge contract.
+
+use soroban_sdk::{contractclient, contractimpl, contracttype, symbol_short, Address, Bytes, BytesN, Env};
+
+use crate::{MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError, TransactionKind};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BridgeMessage {
+    pub destination_chain: u32,
+    /// Recipient on the destination chain, in that chain's address encoding.
+    pub recipient: Bytes,
+    pub payload: Bytes,
+}
+
+#[contractclient(name = "BridgeClient")]
+pub trait Bridge {
+    /// Queues `payload` for delivery and returns the bridge's message sequence.
+    fn send_message(
+        env: Env,
+        sender: Address,
+        destination_chain: u32,
+        recipient: Bytes,
+        payload: Bytes,
+    ) -> u64;
+}
+
+impl MultisigContract {
+    /// Sends an approved message; `bridge` is the one captured at submission and
+    /// must still be the configured bridge.
+    pub(crate) fn execute_bridge_message(
+        env: &Env,
+        transaction_id: u32,
+        bridge: &Address,
+        message: BridgeMessage,
+    ) -> Result<(), MultisigError> {
+        let config = Self::get_config(env)?;
+        if config.bridge.as_ref() != Some(bridge) {
+            return Err(MultisigError::BridgeNotConfigured);
+        }
+
+        let sequence = BridgeClient::new(env, bridge).send_message(
+            &env.current_contract_address(),
+            &message.destination_chain,
+            &message.recipient,
+            &message.payload,
+        );
+
+        Self::publish_event(
+            env,
+            (symbol_short!("bridge"), transaction_id),
+            (message.destination_chain, sequence)
+        );
+
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    pub fn submit_bridge_message(
+        env: Env,
+        caller: Address,
+        message: BridgeMessage,
+    ) -> Result<u32, MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        let bridge = Self::get_config(&env)?.bridge.ok_or(MultisigError::BridgeNotConfigured)?;
+
+        let data = BytesN::from_array(&env, &[0; 32]);
+        Self::store_transaction(&env, &caller, bridge.clone(), 0, bridge, data, TransactionKind::BridgeMessage(message))
+    }
+}
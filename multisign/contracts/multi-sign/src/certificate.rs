@@ -0,0 +1,80 @@
+//! Quorum certificates. When a proposal reaches quorum the wallet records a
+//! compact certificate of the decision, which other contracts can read to
+//! confirm it passed this wallet's governance without walking its votes.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, BytesN, Env, Symbol, Vec};
+
+use crate::{MultisigConfig, MultisigContract, MultisigContractArgs, MultisigContractClient, Transaction};
+
+pub(crate) const CERTIFICATE_KEY: Symbol = symbol_short!("qcert");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QuorumCertificate {
+    pub transaction_id: u32,
+    /// Hash of the proposal content the approvals were given for.
+    pub content_hash: BytesN<32>,
+    /// Config epoch the approvals were given under.
+    pub epoch: u32,
+    /// SHA-256 of the XDR-encoded list of approvers, owners in seat order
+    /// followed by the policy co-signer if it approved.
+    pub approver_set_hash: BytesN<32>,
+    pub approvals: u32,
+    pub issued_at: u32,
+}
+
+impl MultisigContract {
+    /// Everyone whose approval counts towards `transaction_id`.
+    fn counted_approvers(
+        env: &Env,
+        config: &MultisigConfig,
+        transaction_id: u32,
+        transaction: &Transaction,
+    ) -> Vec<Address> {
+        let mut approvers = Vec::new(env);
+        for approver in Self::approvers(env, config, transaction_id).iter() {
+            if Self::approval_condition_met(env, transaction_id, transaction, &approver) {
+                approvers.push_back(approver);
+            }
+        }
+        if Self::effective_approvals(env, transaction_id, transaction) > Self::owner_approvals(env, transaction_id, transaction) {
+            if let Some(policy) = Self::get_policy_signer(env.clone()) {
+                approvers.push_back(policy);
+            }
+        }
+        approvers
+    }
+
+    pub(crate) fn issue_quorum_certificate(
+        env: &Env,
+        config: &MultisigConfig,
+        transaction_id: u32,
+        transaction: &Transaction,
+        approvals: u32,
+    ) {
+        let approvers = Self::counted_approvers(env, config, transaction_id, transaction);
+        let certificate = QuorumCertificate {
+            transaction_id,
+            content_hash: Self::proposal_hash(env, transaction),
+            epoch: config.epoch,
+            approver_set_hash: env.crypto().sha256(&approvers.to_xdr(env)).into(),
+            approvals,
+            issued_at: env.ledger().sequence(),
+        };
+        env.storage().persistent().set(&(CERTIFICATE_KEY, transaction_id), &certificate);
+    }
+
+    pub(crate) fn withdraw_quorum_certificate(env: &Env, transaction_id: u32) {
+        env.storage().persistent().remove(&(CERTIFICATE_KEY, transaction_id));
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Certificate recorded when the proposal reached quorum, withdrawn if
+    /// approvals are revoked below it or the proposal is cancelled. It attests
+    /// the decision, not that the proposal was executed.
+    pub fn get_quorum_certificate(env: Env, transaction_id: u32) -> Option<QuorumCertificate> {
+        env.storage().persistent().get(&(CERTIFICATE_KEY, transaction_id))
+    }
+}
@@ -0,0 +1,121 @@
+//! Checklists: attestations a proposal of a given kind needs from designated
+//! attesters, e.g. "legal-reviewed" or "budget-checked". Until every item is
+//! attested, the proposal's approvals do not count towards execution.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+use crate::{
+    MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError, ProposalKind,
+    Transaction, TX_KEY,
+};
+
+const CHECKLIST_KEY: Symbol = symbol_short!("checklist");
+pub(crate) const ATTESTATION_KEY: Symbol = symbol_short!("attest");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChecklistItem {
+    pub flag: Symbol,
+    /// Addresses allowed to attest this item; they need not be owners.
+    pub attesters: Vec<Address>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Attestation {
+    pub flag: Symbol,
+    pub attester: Address,
+    pub attested_at: u32,
+}
+
+impl MultisigContract {
+    pub(crate) fn set_checklist(
+        env: &Env,
+        kind: ProposalKind,
+        items: Vec<ChecklistItem>,
+    ) -> Result<(), MultisigError> {
+        let key = (CHECKLIST_KEY, kind);
+        if items.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            for (index, item) in items.iter().enumerate() {
+                if item.attesters.is_empty()
+                    || items.iter().skip(index + 1).any(|other| other.flag == item.flag)
+                {
+                    return Err(MultisigError::InvalidMetadata);
+                }
+            }
+            env.storage().persistent().set(&key, &items);
+        }
+
+        Self::publish_event(env, (symbol_short!("checklist"), kind), items.len());
+
+        Ok(())
+    }
+
+    fn get_attestations_for(env: &Env, transaction_id: u32) -> Vec<Attestation> {
+        env.storage().persistent().get(&(ATTESTATION_KEY, transaction_id))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Sets checklist item `flag` on a pending proposal. Only the item's
+    /// designated attesters may do so, once per item.
+    pub fn attest(
+        env: Env,
+        caller: Address,
+        transaction_id: u32,
+        flag: Symbol,
+    ) -> Result<(), MultisigError> {
+        caller.require_auth();
+
+        let transaction: Transaction = env.storage().persistent().get(&(TX_KEY, transaction_id))
+            .ok_or(MultisigError::TransactionNotFound)?;
+        Self::ensure_pending(&env, &transaction)?;
+
+        let item = Self::get_checklist(env.clone(), transaction.kind.proposal_kind())
+            .iter()
+            .find(|item| item.flag == flag)
+            .ok_or(MultisigError::InvalidMetadata)?;
+        if !item.attesters.contains(&caller) {
+            return Err(MultisigError::Unauthorized);
+        }
+
+        let mut attestations = Self::get_attestations_for(&env, transaction_id);
+        if attestations.iter().any(|attestation| attestation.flag == flag) {
+            return Err(MultisigError::AlreadyApproved);
+        }
+        attestations.push_back(Attestation {
+            flag: flag.clone(),
+            attester: caller.clone(),
+            attested_at: env.ledger().sequence(),
+        });
+        env.storage().persistent().set(&(ATTESTATION_KEY, transaction_id), &attestations);
+
+        Self::publish_event(&env, (symbol_short!("attest"), transaction_id), (flag, caller));
+
+        Ok(())
+    }
+
+    pub fn get_checklist(env: Env, kind: ProposalKind) -> Vec<ChecklistItem> {
+        env.storage().persistent().get(&(CHECKLIST_KEY, kind)).unwrap_or_else(|| Vec::new(&env))
+    }
+
+    pub fn get_attestations(env: Env, transaction_id: u32) -> Vec<Attestation> {
+        Self::get_attestations_for(&env, transaction_id)
+    }
+
+    /// Whether every item on the current checklist for the proposal's kind
+    /// has been attested.
+    pub fn is_checklist_complete(env: Env, transaction_id: u32) -> bool {
+        let Some(transaction) = env.storage().persistent().get::<_, Transaction>(&(TX_KEY, transaction_id)) else {
+            return false;
+        };
+        let attestations = Self::get_attestations_for(&env, transaction_id);
+        Self::get_checklist(env.clone(), transaction.kind.proposal_kind())
+            .iter()
+            .all(|item| attestations.iter().any(|attestation| attestation.flag == item.flag))
+    }
+}
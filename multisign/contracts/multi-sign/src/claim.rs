@@ -0,0 +1,146 @@
+//! Delayed claims. A `Claimable` proposal does not push tokens on execution;
+//! it sets them aside for the recipient to pull with `claim`, which suits
+//! recipients that cannot receive the token yet. Unclaimed entries go back
+//! to the wallet once their claim window ends.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, token, Address, BytesN, Env, Symbol};
+
+use crate::{
+    MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError, Transaction,
+    TransactionKind,
+};
+
+pub(crate) const CLAIMABLE_KEY: Symbol = symbol_short!("claimable");
+const CLAIMS_HELD_KEY: Symbol = symbol_short!("claim_hld");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimableEntry {
+    pub recipient: Address,
+    pub token: Address,
+    pub amount: i128,
+    /// Last ledger at which the recipient may claim.
+    pub claim_by: u32,
+}
+
+impl MultisigContract {
+    /// Earmarks an approved payment, subject to the same checks as a transfer.
+    pub(crate) fn create_claimable(
+        env: &Env,
+        transaction_id: u32,
+        transaction: &Transaction,
+        claim_window: u32,
+    ) -> Result<(), MultisigError> {
+        Self::check_token_policy(env, &transaction.token, &transaction.to, transaction.amount)?;
+
+        let config = Self::get_config(env)?;
+        let unanimous = Self::owner_approvals(env, transaction_id, transaction) >= Self::voting_weight(&config);
+        Self::check_savings_lock(env, &transaction.token, transaction.amount, unanimous)?;
+        Self::check_reserved_balance(env, &transaction.token, transaction.amount)?;
+        Self::ensure_token_not_paused(env, &transaction.token)?;
+
+        let entry = ClaimableEntry {
+            recipient: transaction.to.clone(),
+            token: transaction.token.clone(),
+            amount: transaction.amount,
+            claim_by: env.ledger().sequence().checked_add(claim_window)
+                .ok_or(MultisigError::ArithmeticError)?,
+        };
+        Self::adjust_claims_held(env, &entry.token, entry.amount)?;
+        env.storage().persistent().set(&(CLAIMABLE_KEY, transaction_id), &entry);
+
+        Self::publish_event(env, (symbol_short!("claimable"), transaction_id), (entry.recipient, entry.claim_by));
+
+        Ok(())
+    }
+
+    fn adjust_claims_held(env: &Env, token: &Address, delta: i128) -> Result<(), MultisigError> {
+        let held = Self::get_claims_held(env.clone(), token.clone())
+            .checked_add(delta)
+            .ok_or(MultisigError::ArithmeticError)?;
+        env.storage().persistent().set(&(CLAIMS_HELD_KEY, token.clone()), &held);
+
+        Ok(())
+    }
+
+    fn take_claimable(env: &Env, transaction_id: u32) -> Result<ClaimableEntry, MultisigError> {
+        let key = (CLAIMABLE_KEY, transaction_id);
+        let entry: ClaimableEntry = env.storage().persistent().get(&key)
+            .ok_or(MultisigError::TransactionNotFound)?;
+        env.storage().persistent().remove(&key);
+        Self::adjust_claims_held(env, &entry.token, -entry.amount)?;
+
+        Ok(entry)
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Proposes a payment that `to` claims within `claim_window` ledgers of
+    /// execution.
+    pub fn submit_claimable(
+        env: Env,
+        caller: Address,
+        to: Address,
+        amount: i128,
+        token: Address,
+        data: BytesN<32>,
+        claim_window: u32,
+    ) -> Result<u32, MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        if claim_window == 0 {
+            return Err(MultisigError::InvalidSchedule);
+        }
+        Self::validate_transaction_inputs(&env, &to, amount, &token)?;
+
+        Self::store_transaction(&env, &caller, to, amount, token, data, TransactionKind::Claimable(claim_window))
+    }
+
+    /// Pays out an entry to its recipient before its claim window ends.
+    pub fn claim(env: Env, transaction_id: u32) -> Result<i128, MultisigError> {
+        let entry: ClaimableEntry = env.storage().persistent().get(&(CLAIMABLE_KEY, transaction_id))
+            .ok_or(MultisigError::TransactionNotFound)?;
+        entry.recipient.require_auth();
+
+        if env.ledger().sequence() > entry.claim_by {
+            return Err(MultisigError::TransactionExpired);
+        }
+        Self::ensure_not_locked_down(&env).map_err(|error| Self::multisig_error(&env, error))?;
+        Self::ensure_token_not_paused(&env, &entry.token)?;
+
+        let entry = Self::take_claimable(&env, transaction_id)?;
+        token::Client::new(&env, &entry.token)
+            .transfer(&env.current_contract_address(), &entry.recipient, &entry.amount);
+
+        Self::publish_event(&env, (symbol_short!("claimed"), transaction_id), (entry.recipient, entry.amount));
+
+        Ok(entry.amount)
+    }
+
+    /// Permissionless: releases an unclaimed entry back to the wallet once its
+    /// claim window has ended.
+    pub fn reclaim_expired(env: Env, transaction_id: u32) -> Result<i128, MultisigError> {
+        let entry: ClaimableEntry = env.storage().persistent().get(&(CLAIMABLE_KEY, transaction_id))
+            .ok_or(MultisigError::TransactionNotFound)?;
+        if env.ledger().sequence() <= entry.claim_by {
+            return Err(MultisigError::PaymentNotDue);
+        }
+
+        let entry = Self::take_claimable(&env, transaction_id)?;
+
+        Self::publish_event(&env, (symbol_short!("claim_ret"), transaction_id), (entry.recipient, entry.amount));
+
+        Ok(entry.amount)
+    }
+
+    pub fn get_claimable(env: Env, transaction_id: u32) -> Option<ClaimableEntry> {
+        env.storage().persistent().get(&(CLAIMABLE_KEY, transaction_id))
+    }
+
+    /// Total of a token set aside for open claims.
+    pub fn get_claims_held(env: Env, token: Address) -> i128 {
+        env.storage().persistent().get(&(CLAIMS_HELD_KEY, token)).unwrap_or(0)
+    }
+}
@@ -0,0 +1,61 @@
+//! Scheduled configuration changes. A change such as a board rotation can be
+//! proposed and approved ahead of time, to take effect from a known ledger.
+//! Until then it cannot be executed, and cancels like any pending proposal.
+
+use soroban_sdk::{contractimpl, symbol_short, Address, BytesN, Env, Error, Symbol};
+
+use crate::{
+    ConfigChange, ExtendedError, MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError, Transaction,
+    TransactionKind, TX_KEY,
+};
+
+pub(crate) const EFFECTIVE_AT_KEY: Symbol = symbol_short!("cfg_at");
+
+impl MultisigContract {
+    /// Rejects executing a scheduled change before its ledger, with
+    /// `ChangeNotEffective`.
+    pub(crate) fn ensure_change_effective(env: &Env, transaction_id: u32) -> Result<(), Error> {
+        match Self::get_change_effective_at(env.clone(), transaction_id) {
+            Some(effective_at) if env.ledger().sequence() < effective_at => {
+                Err(ExtendedError::ChangeNotEffective.into())
+            },
+            _ => Ok(()),
+        }
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Like `submit_config_change`, for a change that may be executed from
+    /// `effective_at` on. The proposal must not expire before then.
+    pub fn submit_scheduled_config_change(
+        env: Env,
+        caller: Address,
+        change: ConfigChange,
+        effective_at: u32,
+    ) -> Result<u32, MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+        if effective_at <= env.ledger().sequence() {
+            return Err(MultisigError::InvalidSchedule);
+        }
+
+        let this = env.current_contract_address();
+        let data = BytesN::from_array(&env, &[0; 32]);
+        let id = Self::store_transaction(&env, &caller, this.clone(), 0, this, data, TransactionKind::Config(change))?;
+        let transaction: Transaction = env.storage().persistent().get(&(TX_KEY, id))
+            .ok_or(MultisigError::TransactionNotFound)?;
+        if transaction.expires_at.is_some_and(|expires_at| expires_at < effective_at) {
+            return Err(MultisigError::InvalidSchedule);
+        }
+        env.storage().persistent().set(&(EFFECTIVE_AT_KEY, id), &effective_at);
+        Self::publish_event(&env, (symbol_short!("cfg_sched"), id), effective_at);
+
+        Ok(id)
+    }
+
+    /// Ledger from which a scheduled change may be executed.
+    pub fn get_change_effective_at(env: Env, transaction_id: u32) -> Option<u32> {
+        env.storage().persistent().get(&(EFFECTIVE_AT_KEY, transaction_id))
+    }
+}
@@ -0,0 +1,136 @@
+//! Contract call proposals. The wallet can govern any Soroban contract, not
+//! only token transfers: an approved proposal invokes the named function with
+//! the given arguments as the wallet, and the value it returns is kept for
+//! anyone to read back. Calls that move the wallet's tokens, such as a
+//! `transfer` or `burn`, are held to the rules of a transfer of that token.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, BytesN, Env, Symbol, TryFromVal, Val, Vec};
+
+use crate::{
+    MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError, Transaction, TransactionKind,
+};
+
+pub(crate) const CALL_RESULT_KEY: Symbol = symbol_short!("call_res");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractCall {
+    pub contract: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
+}
+
+impl MultisigContract {
+    /// The recipient and amount of a SEP-41 call that moves tokens: the
+    /// destination of a `transfer` or `transfer_from`, the spender of an
+    /// `approve`, or the token itself for a `burn` or `burn_from`. `None` for
+    /// any other function.
+    pub(crate) fn token_movement(env: &Env, call: &ContractCall) -> Result<Option<(Address, i128)>, MultisigError> {
+        // Argument positions of (recipient, amount); burns have no recipient.
+        let (to_index, amount_index) = if call.function == symbol_short!("transfer")
+            || call.function == symbol_short!("approve")
+        {
+            (Some(1), 2)
+        } else if call.function == Symbol::new(env, "transfer_from") {
+            (Some(2), 3)
+        } else if call.function == symbol_short!("burn") {
+            (None, 1)
+        } else if call.function == symbol_short!("burn_from") {
+            (None, 2)
+        } else {
+            return Ok(None);
+        };
+
+        let to = match to_index {
+            Some(index) => call.args.get(index)
+                .and_then(|val| Address::try_from_val(env, &val).ok())
+                .ok_or(MultisigError::InvalidAddress)?,
+            None => call.contract.clone(),
+        };
+        let amount = call.args.get(amount_index)
+            .and_then(|val| i128::try_from_val(env, &val).ok())
+            .ok_or(MultisigError::InvalidAmount)?;
+
+        Ok(Some((to, amount)))
+    }
+
+    /// `leg` seen as the transfer its call amounts to, if it moves tokens.
+    pub(crate) fn token_call_as_transfer(
+        env: &Env,
+        leg: &Transaction,
+        call: &ContractCall,
+    ) -> Result<Option<Transaction>, MultisigError> {
+        let Some((to, amount)) = Self::token_movement(env, call)? else {
+            return Ok(None);
+        };
+
+        let token = call.contract.clone();
+        Ok(Some(Transaction { to, amount, token, kind: TransactionKind::Transfer, ..leg.clone() }))
+    }
+
+    pub(crate) fn validate_contract_call(env: &Env, call: &ContractCall) -> Result<(), MultisigError> {
+        // Calls into the wallet itself would skip its own proposal rules.
+        if call.contract == env.current_contract_address() || Self::is_unusable_address(env, &call.contract) {
+            return Err(MultisigError::InvalidAddress);
+        }
+        if let Some((to, amount)) = Self::token_movement(env, call)? {
+            Self::validate_transaction_inputs(env, &to, amount, &call.contract)?;
+        }
+
+        Ok(())
+    }
+
+    /// Makes an approved call, first applying the checks of a transfer if it
+    /// moves tokens. A call the target rejects fails the execution, leaving
+    /// the proposal pending.
+    pub(crate) fn execute_contract_call(
+        env: &Env,
+        transaction_id: u32,
+        leg: &Transaction,
+        call: ContractCall,
+    ) -> Result<(), MultisigError> {
+        if let Some(transfer) = Self::token_call_as_transfer(env, leg, &call)? {
+            Self::screen_recipient(env, transaction_id, &transfer, true)?;
+            Self::check_outflow(env, transaction_id, &transfer)?;
+        }
+
+        let result: Val = env.invoke_contract(&call.contract, &call.function, call.args);
+        env.storage().persistent().set(&(CALL_RESULT_KEY, transaction_id), &result);
+
+        Self::publish_event(env, (symbol_short!("call"), transaction_id), (call.contract, call.function, result));
+
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Proposes invoking `call.function` on `call.contract` as the wallet.
+    pub fn submit_contract_call(
+        env: Env,
+        caller: Address,
+        call: ContractCall,
+    ) -> Result<u32, MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        Self::validate_contract_call(&env, &call)?;
+
+        let data = BytesN::from_array(&env, &[0; 32]);
+        Self::store_transaction(
+            &env,
+            &caller,
+            call.contract.clone(),
+            0,
+            call.contract.clone(),
+            data,
+            TransactionKind::ContractCall(call),
+        )
+    }
+
+    /// Value returned by an executed contract call proposal; for a batch,
+    /// by its last call.
+    pub fn get_call_result(env: Env, transaction_id: u32) -> Option<Val> {
+        env.storage().persistent().get(&(CALL_RESULT_KEY, transaction_id))
+    }
+}
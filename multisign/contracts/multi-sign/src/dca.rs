@@ -0,0 +1,161 @@
+//! Dollar-cost averaging: the quorum approves a plan to sell a fixed amount of
+//! one token for another every period, and any keeper can trigger each tranche
+//! once it is due.
+
+use soroban_sdk::{
+    contractclient, contractimpl, contracttype, symbol_short, token, vec, Address, Env, Symbol, Vec,
+};
+
+use crate::{MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError};
+
+const DCA_KEY: Symbol = symbol_short!("dca");
+pub(crate) const DCA_COUNT_KEY: Symbol = symbol_short!("dca_cnt");
+
+const BPS_DENOMINATOR: i128 = 10_000;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DcaPlan {
+    /// Router used for every tranche; it must pull `sell_token` with `transfer_from`.
+    pub router: Address,
+    pub sell_token: Address,
+    pub buy_token: Address,
+    pub amount_per_tranche: i128,
+    pub total_tranches: u32,
+    /// Ledgers between tranches.
+    pub interval: u32,
+    pub next_due: u32,
+    /// Largest accepted shortfall against the router quote, in basis points.
+    pub max_slippage_bps: u32,
+    pub tranches_done: u32,
+    pub total_sold: i128,
+    pub total_bought: i128,
+}
+
+/// The subset of the Soroswap router interface used for tranches.
+#[contractclient(name = "SwapRouterClient")]
+pub trait SwapRouter {
+    fn router_get_amounts_out(env: Env, amount_in: i128, path: Vec<Address>) -> Vec<i128>;
+    fn swap_exact_tokens_for_tokens(
+        env: Env,
+        amount_in: i128,
+        amount_out_min: i128,
+        path: Vec<Address>,
+        to: Address,
+        deadline: u64,
+    ) -> Vec<i128>;
+}
+
+impl MultisigContract {
+    pub(crate) fn create_dca_plan(env: &Env, mut plan: DcaPlan) -> Result<(), MultisigError> {
+        if plan.amount_per_tranche <= 0 {
+            return Err(MultisigError::InvalidAmount);
+        }
+        if plan.interval == 0 || plan.total_tranches == 0 || plan.max_slippage_bps as i128 >= BPS_DENOMINATOR {
+            return Err(MultisigError::InvalidSchedule);
+        }
+        if plan.sell_token == plan.buy_token {
+            return Err(MultisigError::InvalidAddress);
+        }
+
+        plan.tranches_done = 0;
+        plan.total_sold = 0;
+        plan.total_bought = 0;
+
+        let count: u32 = env.storage().persistent().get(&DCA_COUNT_KEY).unwrap_or(0);
+        let plan_id = count.checked_add(1).ok_or(MultisigError::ArithmeticError)?;
+        env.storage().persistent().set(&DCA_COUNT_KEY, &plan_id);
+        env.storage().persistent().set(&(DCA_KEY, plan_id), &plan);
+
+        Self::publish_event(env, (symbol_short!("dca_new"), plan_id), plan);
+
+        Ok(())
+    }
+
+    pub(crate) fn cancel_dca_plan(env: &Env, plan_id: u32) -> Result<(), MultisigError> {
+        let key = (DCA_KEY, plan_id);
+        if !env.storage().persistent().has(&key) {
+            return Err(MultisigError::DcaPlanNotFound);
+        }
+        env.storage().persistent().remove(&key);
+
+        Self::publish_event(env, (symbol_short!("dca_end"), plan_id), ());
+
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Sells the next tranche of a DCA plan through its router. Callable by
+    /// anyone once the tranche is due; returns the amount of `buy_token` received.
+    pub fn execute_dca_tranche(env: Env, plan_id: u32) -> Result<i128, MultisigError> {
+        Self::ensure_not_locked_down(&env).map_err(|error| Self::multisig_error(&env, error))?;
+        let key = (DCA_KEY, plan_id);
+        let mut plan: DcaPlan = env.storage().persistent().get(&key)
+            .ok_or(MultisigError::DcaPlanNotFound)?;
+
+        if plan.tranches_done >= plan.total_tranches {
+            return Err(MultisigError::DcaPlanCompleted);
+        }
+        if env.ledger().sequence() < plan.next_due {
+            return Err(MultisigError::PaymentNotDue);
+        }
+
+        Self::check_savings_lock(&env, &plan.sell_token, plan.amount_per_tranche, false)?;
+        Self::check_reserved_balance(&env, &plan.sell_token, plan.amount_per_tranche)?;
+        Self::ensure_token_not_paused(&env, &plan.sell_token)?;
+
+        let this = env.current_contract_address();
+        let router = SwapRouterClient::new(&env, &plan.router);
+        let path = vec![&env, plan.sell_token.clone(), plan.buy_token.clone()];
+
+        let quoted = router.router_get_amounts_out(&plan.amount_per_tranche, &path)
+            .last()
+            .ok_or(MultisigError::SlippageExceeded)?;
+        let min_out = quoted
+            .checked_mul(BPS_DENOMINATOR - plan.max_slippage_bps as i128)
+            .ok_or(MultisigError::ArithmeticError)?
+            / BPS_DENOMINATOR;
+
+        // Allowance only lives for this ledger, so the router can take exactly one tranche.
+        token::Client::new(&env, &plan.sell_token)
+            .approve(&this, &plan.router, &plan.amount_per_tranche, &env.ledger().sequence());
+
+        let buy_client = token::Client::new(&env, &plan.buy_token);
+        let balance_before = buy_client.balance(&this);
+        router.swap_exact_tokens_for_tokens(
+            &plan.amount_per_tranche,
+            &min_out,
+            &path,
+            &this,
+            &env.ledger().timestamp(),
+        );
+        let bought = buy_client.balance(&this) - balance_before;
+        if bought < min_out {
+            return Err(MultisigError::SlippageExceeded);
+        }
+
+        plan.tranches_done += 1;
+        plan.next_due = plan.next_due.checked_add(plan.interval)
+            .ok_or(MultisigError::ArithmeticError)?;
+        plan.total_sold = plan.total_sold.checked_add(plan.amount_per_tranche)
+            .ok_or(MultisigError::ArithmeticError)?;
+        plan.total_bought = plan.total_bought.checked_add(bought)
+            .ok_or(MultisigError::ArithmeticError)?;
+        env.storage().persistent().set(&key, &plan);
+
+        Self::publish_event(
+            &env,
+            (symbol_short!("dca"), plan_id),
+            (plan.tranches_done, plan.amount_per_tranche, bought)
+        );
+
+        Ok(bought)
+    }
+
+    pub fn get_dca_plan(env: Env, plan_id: u32) -> Result<DcaPlan, MultisigError> {
+        env.storage().persistent().get(&(DCA_KEY, plan_id))
+            .ok_or(MultisigError::DcaPlanNotFound)
+    }
+}
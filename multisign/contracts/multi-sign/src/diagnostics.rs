@@ -0,0 +1,114 @@
+//! Error diagnostics. A failed call rolls back its events and storage, and
+//! leaves callers with a bare error code. These views rerun the checks of a
+//! submission or execution against current state and report which input
+//! failed, and with what value where one applies, before anything is signed.
+
+use soroban_sdk::{contractimpl, contracttype, Address, BytesN, Env, Error, Symbol};
+
+use crate::{
+    ExtendedError, MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError, Transaction, TX_KEY,
+};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ErrorInfo {
+    /// The `MultisigError` or `ExtendedError` the call would fail with.
+    pub code: u32,
+    /// Name of the argument or proposal field at fault.
+    pub field: Symbol,
+    /// The offending value, or the one the check compared against.
+    pub value: Option<i128>,
+}
+
+impl MultisigContract {
+    fn error_info(env: &Env, error: impl Into<Error>, field: &str, value: Option<i128>) -> ErrorInfo {
+        ErrorInfo { code: error.into().get_code(), field: Symbol::new(env, field), value }
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Why `submit_transaction` would reject this transfer from `caller`, or
+    /// `None` if it would be accepted.
+    pub fn diagnose_submission(
+        env: Env,
+        caller: Address,
+        to: Address,
+        amount: i128,
+        token: Address,
+        data: BytesN<32>,
+    ) -> Option<ErrorInfo> {
+        let Ok(config) = Self::get_config(&env) else {
+            return Some(Self::error_info(&env, MultisigError::Unauthorized, "wallet", None));
+        };
+        if !config.owners.contains(&caller) || !Self::owner_role(&config, &caller).can_propose() {
+            return Some(Self::error_info(&env, MultisigError::Unauthorized, "caller", None));
+        }
+
+        let error = Self::validate_submission(env.clone(), to, amount, token, data).err()?;
+        let info = match error {
+            MultisigError::InvalidAmount | MultisigError::AmountImplausible | MultisigError::AmountExceedsLimit => {
+                Self::error_info(&env, error, "amount", Some(amount))
+            },
+            MultisigError::InvalidToken => Self::error_info(&env, error, "token", None),
+            MultisigError::InvalidAddress | MultisigError::RecipientNotAllowed => {
+                Self::error_info(&env, error, "to", None)
+            },
+            MultisigError::DuplicateTransaction => Self::error_info(&env, error, "data", None),
+            _ => Self::error_info(&env, error, "transfer", None),
+        };
+
+        Some(info)
+    }
+
+    /// Why `execute_transaction` by `caller` would fail before running the
+    /// proposal, or `None` if it would get that far.
+    pub fn diagnose_execution(env: Env, caller: Address, transaction_id: u32) -> Option<ErrorInfo> {
+        let Ok(config) = Self::get_config(&env) else {
+            return Some(Self::error_info(&env, MultisigError::Unauthorized, "wallet", None));
+        };
+        let Some(transaction) = env.storage().persistent().get::<_, Transaction>(&(TX_KEY, transaction_id)) else {
+            let id = Some(transaction_id.into());
+            return Some(Self::error_info(&env, MultisigError::TransactionNotFound, "transaction_id", id));
+        };
+        if !config.owners.contains(&caller) || !Self::owner_role(&config, &caller).can_execute() {
+            return Some(Self::error_info(&env, MultisigError::Unauthorized, "caller", None));
+        }
+
+        let error = Self::authorize_execution(&env, &config, &caller, transaction_id, &transaction).err()?;
+        let info = match MultisigError::try_from(error) {
+            Ok(
+                MultisigError::TransactionExecuted
+                | MultisigError::TransactionCancelled
+                | MultisigError::TransactionExpired
+                | MultisigError::Unauthorized,
+            ) => Self::error_info(&env, error, "status", None),
+            Ok(MultisigError::SubmitterCannotExecute) => Self::error_info(&env, error, "caller", None),
+            Ok(MultisigError::InsufficientApprovals) => {
+                let approvals = Self::effective_approvals(&env, transaction_id, &transaction);
+                Self::error_info(&env, error, "approvals", Some(approvals.into()))
+            },
+            Ok(MultisigError::AmountExceedsLimit) => {
+                Self::error_info(&env, error, "amount", Some(transaction.amount))
+            },
+            Ok(_) => Self::error_info(&env, error, "wallet", None),
+            Err(_) => match ExtendedError::try_from(error) {
+                Ok(ExtendedError::OutOfOrder) => {
+                    let blocker = Self::get_ordering_blocker(env.clone(), transaction_id);
+                    Self::error_info(&env, error, "blocker", blocker.map(i128::from))
+                },
+                Ok(ExtendedError::EarlyExecution) => {
+                    let eta = Self::get_execution_eta(env.clone(), transaction_id).ok().flatten();
+                    Self::error_info(&env, error, "eta", eta.map(i128::from))
+                },
+                Ok(ExtendedError::ChangeNotEffective) => {
+                    let effective_at = Self::get_change_effective_at(env.clone(), transaction_id);
+                    Self::error_info(&env, error, "effective_at", effective_at.map(i128::from))
+                },
+                _ => Self::error_info(&env, error, "wallet", None),
+            },
+        };
+
+        Some(info)
+    }
+}
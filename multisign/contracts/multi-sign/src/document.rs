@@ -0,0 +1,76 @@
+//! Quorum attestation of off-chain documents by hash, with no funds moved.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, BytesN, Env, Symbol};
+
+use crate::{MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError, TransactionKind};
+
+const DOCUMENT_KEY: Symbol = symbol_short!("document");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DocumentSignature {
+    pub transaction_id: u32,
+    pub signed_at: u32,
+}
+
+impl MultisigContract {
+    pub(crate) fn record_document_signature(
+        env: &Env,
+        transaction_id: u32,
+        document_hash: BytesN<32>,
+    ) -> Result<(), MultisigError> {
+        let key = (DOCUMENT_KEY, document_hash.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(MultisigError::DocumentAlreadySigned);
+        }
+
+        let signature = DocumentSignature {
+            transaction_id,
+            signed_at: env.ledger().sequence(),
+        };
+        env.storage().persistent().set(&key, &signature);
+
+        Self::publish_event(env, (symbol_short!("doc_sign"), document_hash), transaction_id);
+
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    pub fn submit_document(
+        env: Env,
+        caller: Address,
+        document_hash: BytesN<32>,
+    ) -> Result<u32, MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        if env.storage().persistent().has(&(DOCUMENT_KEY, document_hash.clone())) {
+            return Err(MultisigError::DocumentAlreadySigned);
+        }
+
+        let this = env.current_contract_address();
+        Self::store_transaction(
+            &env,
+            &caller,
+            this.clone(),
+            0,
+            this,
+            document_hash.clone(),
+            TransactionKind::SignDocument(document_hash),
+        )
+    }
+
+    pub fn is_document_signed(env: Env, document_hash: BytesN<32>) -> bool {
+        env.storage().persistent().has(&(DOCUMENT_KEY, document_hash))
+    }
+
+    pub fn get_document_signature(
+        env: Env,
+        document_hash: BytesN<32>,
+    ) -> Result<DocumentSignature, MultisigError> {
+        env.storage().persistent().get(&(DOCUMENT_KEY, document_hash))
+            .ok_or(MultisigError::DocumentNotSigned)
+    }
+}
@@ -0,0 +1,108 @@
+//! Draft transfers. A submitter can stage a transfer, fix its recipient, amount
+//! or memo, and only then open it for approval, instead of cancelling and
+//! resubmitting over a typo. Drafts take no votes and do not count as pending.
+
+use soroban_sdk::{contractimpl, symbol_short, Address, BytesN, Env};
+
+use crate::{
+    MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError, OwnerRole,
+    Transaction, TransactionKind, TransactionStatus, TX_KEY,
+};
+
+impl MultisigContract {
+    /// The caller's own draft, which only its submitter may touch.
+    fn own_draft(env: &Env, caller: &Address, transaction_id: u32) -> Result<Transaction, MultisigError> {
+        let transaction: Transaction = env.storage().persistent().get(&(TX_KEY, transaction_id))
+            .ok_or(MultisigError::TransactionNotFound)?;
+        if transaction.status != TransactionStatus::Draft || transaction.submitter != *caller {
+            return Err(MultisigError::Unauthorized);
+        }
+
+        Ok(transaction)
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Stages a transfer under a new proposal id without opening it for votes.
+    pub fn submit_draft(
+        env: Env,
+        caller: Address,
+        to: Address,
+        amount: i128,
+        token: Address,
+        data: BytesN<32>,
+    ) -> Result<u32, MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+        Self::ensure_role(&Self::get_config(&env)?, &caller, OwnerRole::can_propose)?;
+        Self::validate_transaction_inputs(&env, &to, amount, &token)?;
+
+        let id = Self::next_transaction_id(&env)?;
+        let transaction = Self::new_transaction(
+            &caller,
+            to.clone(),
+            amount,
+            token.clone(),
+            data,
+            TransactionKind::Transfer,
+            TransactionStatus::Draft,
+        );
+        env.storage().persistent().set(&(TX_KEY, id), &transaction);
+        Self::notify_listener(&env, id, &transaction);
+
+        Self::publish_event(&env, (symbol_short!("draft"), id), (caller, to, amount, token));
+
+        Ok(id)
+    }
+
+    /// Rewrites the recipient, amount and memo of the caller's draft.
+    pub fn amend_draft(
+        env: Env,
+        caller: Address,
+        transaction_id: u32,
+        to: Address,
+        amount: i128,
+        data: BytesN<32>,
+    ) -> Result<(), MultisigError> {
+        caller.require_auth();
+        let mut transaction = Self::own_draft(&env, &caller, transaction_id)?;
+        Self::validate_transaction_inputs(&env, &to, amount, &transaction.token)?;
+
+        transaction.to = to.clone();
+        transaction.amount = amount;
+        transaction.data = data;
+        env.storage().persistent().set(&(TX_KEY, transaction_id), &transaction);
+        Self::notify_listener(&env, transaction_id, &transaction);
+
+        Self::publish_event(&env, (symbol_short!("draft_amd"), transaction_id), (to, amount));
+
+        Ok(())
+    }
+
+    /// Opens the caller's draft for approval as if just submitted; its
+    /// content is frozen from here on.
+    pub fn open_for_approval(env: Env, caller: Address, transaction_id: u32) -> Result<(), MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+        let transaction = Self::own_draft(&env, &caller, transaction_id)?;
+
+        let config = Self::get_config(&env)?;
+        Self::ensure_role(&config, &caller, OwnerRole::can_propose)?;
+        Self::open_transaction(&env, &config, transaction_id, transaction)
+    }
+
+    pub fn discard_draft(env: Env, caller: Address, transaction_id: u32) -> Result<(), MultisigError> {
+        caller.require_auth();
+        let mut transaction = Self::own_draft(&env, &caller, transaction_id)?;
+
+        transaction.status = TransactionStatus::Cancelled;
+        transaction.closed_at = Some(env.ledger().sequence());
+        env.storage().persistent().set(&(TX_KEY, transaction_id), &transaction);
+        Self::notify_listener(&env, transaction_id, &transaction);
+
+        Self::publish_event(&env, (symbol_short!("draft_del"), transaction_id), caller);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+//! Signer drills. A drill proposal goes through the usual submit, approve and
+//! execute flow but moves nothing. Executing it records, for each owner who took
+//! part, when they did, so dead keys are found before an emergency.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, BytesN, Env, Symbol, Vec};
+
+use crate::{
+    MultisigConfig, MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError,
+    TransactionKind,
+};
+
+const DRILL_KEY: Symbol = symbol_short!("drill");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DrillRecord {
+    pub transaction_id: u32,
+    /// Ledger of the owner's approval, or of the execution for an executor
+    /// who did not approve.
+    pub responded_at: u32,
+    pub completed_at: u32,
+}
+
+impl MultisigContract {
+    /// Records every approver's and the executor's participation. Runs before
+    /// the proposal's votes can be pruned.
+    pub(crate) fn complete_drill(env: &Env, config: &MultisigConfig, transaction_id: u32, executor: &Address) {
+        let completed_at = env.ledger().sequence();
+        for owner in config.owners.iter() {
+            let responded_at = match Self::approval_record(env, transaction_id, &owner) {
+                Some(approval) => approval.approved_at,
+                None if owner == *executor => completed_at,
+                None => continue,
+            };
+
+            let record = DrillRecord { transaction_id, responded_at, completed_at };
+            env.storage().persistent().set(&(DRILL_KEY, owner), &record);
+        }
+
+        Self::publish_event(env, (symbol_short!("drill"), transaction_id), executor.clone());
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    pub fn submit_drill(env: Env, caller: Address) -> Result<u32, MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        let this = env.current_contract_address();
+        Self::store_transaction(
+            &env,
+            &caller,
+            this.clone(),
+            0,
+            this,
+            BytesN::from_array(&env, &[0; 32]),
+            TransactionKind::Drill,
+        )
+    }
+
+    /// The last executed drill `owner` took part in.
+    pub fn get_drill_record(env: Env, owner: Address) -> Option<DrillRecord> {
+        env.storage().persistent().get(&(DRILL_KEY, owner))
+    }
+
+    /// Owners who have not completed a drill at or after `since_ledger`.
+    pub fn get_drill_stragglers(env: Env, since_ledger: u32) -> Result<Vec<Address>, MultisigError> {
+        let mut stragglers = Vec::new(&env);
+        for owner in Self::get_config(&env)?.owners.iter() {
+            let record: Option<DrillRecord> = env.storage().persistent().get(&(DRILL_KEY, owner.clone()));
+            if record.is_none_or(|record| record.completed_at < since_ledger) {
+                stragglers.push_back(owner);
+            }
+        }
+        Ok(stragglers)
+    }
+}
@@ -0,0 +1,44 @@
+//! Wallet label for events. An organization running many wallets may give
+//! each a short label, such as `payroll` or `treasury`. While one is set it
+//! leads the topics of every event the wallet publishes, so a single RPC
+//! event stream can be filtered by wallet purpose on the first topic.
+
+use soroban_sdk::{contractimpl, events::Topics, symbol_short, Env, IntoVal, Symbol, Val, Vec};
+
+use crate::{MultisigContract, MultisigContractArgs, MultisigContractClient};
+
+pub(crate) const LABEL_KEY: Symbol = symbol_short!("label");
+
+impl MultisigContract {
+    pub(crate) fn set_event_label(env: &Env, label: Option<Symbol>) {
+        match &label {
+            Some(label) => env.storage().persistent().set(&LABEL_KEY, label),
+            None => env.storage().persistent().remove(&LABEL_KEY),
+        }
+        Self::publish_event(env, (symbol_short!("label"),), label);
+    }
+
+    /// Publishes an event, with the wallet's label in front of `topics` if
+    /// it has one. Every event the wallet emits goes through here.
+    pub(crate) fn publish_event<T, D>(env: &Env, topics: T, data: D)
+    where
+        T: Topics,
+        D: IntoVal<Env, Val>,
+    {
+        match Self::get_event_label(env.clone()) {
+            Some(label) => {
+                let mut labelled: Vec<Val> = topics.into_val(env);
+                labelled.push_front(label.into_val(env));
+                env.events().publish(labelled, data);
+            },
+            None => env.events().publish(topics, data),
+        }
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    pub fn get_event_label(env: Env) -> Option<Symbol> {
+        env.storage().persistent().get(&LABEL_KEY)
+    }
+}
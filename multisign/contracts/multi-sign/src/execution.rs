@@ -0,0 +1,200 @@
+//! Execution engine. An approved proposal is marked executed, then each of its
+//! action legs is tried in turn. Once every leg has succeeded the execution is
+//! confirmed: a config change re-checks which pending proposals are at quorum,
+//! simulation locks are checked, the receipt is issued, the bond and
+//! executor reimbursement are settled, votes are pruned and the listener is
+//! notified. If a leg fails, or panics, the whole call fails and the host
+//! rolls back every write it made, earlier legs included, so the proposal is
+//! still pending exactly as it was and can be executed again once the cause
+//! is fixed. Nothing of a failed attempt is kept.
+
+use soroban_sdk::{contractimpl, panic_with_error, symbol_short, token, vec, Address, Env, Error, Vec};
+
+use crate::{
+    ExtendedError, MultisigConfig, MultisigContract, MultisigContractArgs, MultisigContractClient,
+    MultisigError, OwnerRole, Transaction, TransactionKind, TransactionStatus, TX_KEY,
+};
+
+impl MultisigContract {
+    /// Checks that `caller` may execute the proposal now. Writes nothing, so
+    /// diagnostics can reuse it. Fails with a `MultisigError` or an
+    /// `ExtendedError`.
+    pub(crate) fn authorize_execution(
+        env: &Env,
+        config: &MultisigConfig,
+        caller: &Address,
+        transaction_id: u32,
+        transaction: &Transaction,
+    ) -> Result<(), Error> {
+        Self::ensure_pending(env, transaction)?;
+        Self::ensure_role(config, caller, OwnerRole::can_execute)?;
+
+        Self::ensure_not_frozen(env, &transaction.kind)?;
+
+        if config.dual_control && *caller == transaction.submitter {
+            return Err(MultisigError::SubmitterCannotExecute.into());
+        }
+        Self::ensure_in_order(env, transaction_id)?;
+
+        let approvals = Self::effective_approvals(env, transaction_id, transaction);
+        if approvals < Self::required_approvals_for(env, config, transaction)
+            || !Self::is_checklist_complete(env.clone(), transaction_id)
+        {
+            return Err(MultisigError::InsufficientApprovals.into());
+        }
+        Self::ensure_approvals_current(env, config, transaction_id, transaction)?;
+        Self::ensure_timelock_elapsed(env, transaction)?;
+        Self::ensure_change_effective(env, transaction_id)?;
+
+        Ok(())
+    }
+
+    /// Counts a proposal executing below its standard threshold against the
+    /// fast lane or spending limit that let it through.
+    fn record_threshold_usage(
+        env: &Env,
+        config: &MultisigConfig,
+        transaction_id: u32,
+        transaction: &Transaction,
+    ) -> Result<(), MultisigError> {
+        let approvals = Self::effective_approvals(env, transaction_id, transaction);
+        if approvals < Self::standard_approvals_for(env, config, transaction) {
+            Self::record_reduced_threshold_spend(env, config, transaction)?;
+        }
+
+        Ok(())
+    }
+
+    /// The actions a proposal carries out, in order. A batch contributes one
+    /// leg per operation; anything else is its own single leg.
+    fn execution_legs(env: &Env, transaction: &Transaction) -> Vec<Transaction> {
+        match &transaction.kind {
+            TransactionKind::Batch(operations) => Self::batch_legs(env, transaction, operations.clone()),
+            _ => vec![env, transaction.clone()],
+        }
+    }
+
+    fn run_leg(
+        env: &Env,
+        config: &MultisigConfig,
+        executor: &Address,
+        transaction_id: u32,
+        leg: &Transaction,
+    ) -> Result<(), MultisigError> {
+        if Self::pays_recipient(&leg.kind) {
+            Self::screen_recipient(env, transaction_id, leg, true)?;
+        }
+        match leg.kind.clone() {
+            TransactionKind::Transfer => Self::execute_transfer(env, transaction_id, leg),
+            TransactionKind::Config(change) => Self::apply_config_change(env, transaction_id, change, leg),
+            TransactionKind::Liquidity(action) => Self::execute_liquidity(env, action),
+            TransactionKind::BridgeMessage(message) => {
+                Self::execute_bridge_message(env, transaction_id, &leg.to, message)
+            },
+            TransactionKind::SignDocument(document_hash) => {
+                Self::record_document_signature(env, transaction_id, document_hash)
+            },
+            TransactionKind::Approve(grant) => Self::execute_approve(env, grant),
+            TransactionKind::Claimable(claim_window) => {
+                Self::create_claimable(env, transaction_id, leg, claim_window)
+            },
+            TransactionKind::WrapAsset(serialized_asset) => {
+                Self::wrap_asset(env, transaction_id, serialized_asset).map(|_| ())
+            },
+            TransactionKind::Drill => {
+                Self::complete_drill(env, config, transaction_id, executor);
+                Ok(())
+            },
+            TransactionKind::ContractCall(call) => Self::execute_contract_call(env, transaction_id, leg, call),
+            // Expanded into its operations by `execution_legs`.
+            TransactionKind::Batch(_) => panic_with_error!(env, ExtendedError::InvalidBatch),
+        }
+    }
+
+    /// Rules any movement of the wallet's tokens must pass at execution: the
+    /// token's policy, savings lock, reserved balances and pause switch.
+    pub(crate) fn check_outflow(
+        env: &Env,
+        transaction_id: u32,
+        transaction: &Transaction,
+    ) -> Result<(), MultisigError> {
+        Self::check_token_policy(env, &transaction.token, &transaction.to, transaction.amount)?;
+
+        let config = Self::get_config(env)?;
+        let unanimous = Self::owner_approvals(env, transaction_id, transaction) >= Self::voting_weight(&config);
+        Self::check_savings_lock(env, &transaction.token, transaction.amount, unanimous)?;
+        Self::check_reserved_balance(env, &transaction.token, transaction.amount)?;
+        Self::ensure_token_not_paused(env, &transaction.token)
+    }
+
+    fn execute_transfer(env: &Env, transaction_id: u32, transaction: &Transaction) -> Result<(), MultisigError> {
+        Self::check_outflow(env, transaction_id, transaction)?;
+
+        token::Client::new(env, &transaction.token)
+            .try_transfer(&env.current_contract_address(), &transaction.to, &transaction.amount)
+            .map_err(|_| MultisigError::TokenTransferFailed)?
+            .map_err(|_| MultisigError::TokenTransferFailed)
+    }
+
+    /// Moves the proposal from pending to executed before any leg runs, so
+    /// nothing a leg calls into can execute it a second time.
+    fn begin_execution(env: &Env, transaction_id: u32, transaction: &mut Transaction) {
+        transaction.status = TransactionStatus::Executed;
+        transaction.closed_at = Some(env.ledger().sequence());
+        env.storage().persistent().set(&(TX_KEY, transaction_id), transaction);
+        Self::track_pending(env, transaction_id, false);
+    }
+
+    pub(crate) fn run_execution(env: &Env, caller: &Address, transaction_id: u32) -> Result<(), MultisigError> {
+        let mut transaction: Transaction = env.storage().persistent().get(&(TX_KEY, transaction_id))
+            .ok_or(MultisigError::TransactionNotFound)?;
+        let config = Self::get_config(env)?;
+        Self::authorize_execution(env, &config, caller, transaction_id, &transaction)
+            .map_err(|error| Self::multisig_error(env, error))?;
+        Self::touch_transaction(env, &config, transaction_id);
+
+        Self::begin_execution(env, transaction_id, &mut transaction);
+        Self::record_threshold_usage(env, &config, transaction_id, &transaction)?;
+
+        let balances_before = Self::snapshot_simulated_balances(env, transaction_id);
+        let token_balance_before = Self::receipt_balance(env, &transaction);
+        for leg in Self::execution_legs(env, &transaction).iter() {
+            Self::run_leg(env, &config, caller, transaction_id, &leg)?;
+        }
+
+        if let TransactionKind::Config(_) = transaction.kind {
+            Self::sync_pending_quorums(env)?;
+        }
+        Self::verify_simulation(env, transaction_id, balances_before)?;
+        Self::issue_receipt(env, caller, transaction_id, &transaction, token_balance_before)?;
+        Self::settle_bond(env, transaction_id, &transaction, true)?;
+        Self::reimburse_executor(env, caller, transaction_id)?;
+
+        Self::prune_votes(env, &config, transaction_id, &mut transaction);
+        env.storage().persistent().set(&(TX_KEY, transaction_id), &transaction);
+        Self::advance_order_head(env, transaction_id);
+        Self::notify_listener(env, transaction_id, &transaction);
+
+        Self::publish_event(
+            env,
+            (symbol_short!("execute"), transaction_id),
+            (caller.clone(), transaction.to, transaction.amount, transaction.token)
+        );
+
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    pub fn execute_transaction(
+        env: Env,
+        caller: Address,
+        transaction_id: u32
+    ) -> Result<(), MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        Self::run_execution(&env, &caller, transaction_id)
+    }
+}
@@ -0,0 +1,94 @@
+//! Fast lane for routine micro-payments: small transfers of one token need a
+//! single approval, up to a total per wallet period (a UTC day by default).
+//! Enabling it takes a proposal, but any one owner can switch it off
+//! immediately.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, Env, Symbol};
+
+use crate::{
+    MultisigConfig, MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError,
+    Transaction, TransactionKind, CONFIG_KEY,
+};
+
+const FAST_LANE_KEY: Symbol = symbol_short!("fast_lane");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FastLanePolicy {
+    pub token: Address,
+    /// Largest single transfer eligible for the fast lane.
+    pub max_amount: i128,
+    /// Total the fast lane may move per wallet period.
+    pub daily_limit: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FastLane {
+    Disabled,
+    Enabled(FastLanePolicy),
+}
+
+impl MultisigContract {
+    pub(crate) fn validate_fast_lane(fast_lane: &FastLane) -> Result<(), MultisigError> {
+        if let FastLane::Enabled(policy) = fast_lane {
+            if policy.max_amount <= 0 || policy.daily_limit < policy.max_amount {
+                return Err(MultisigError::InvalidAmount);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether `transaction` can still go through the fast lane this period.
+    pub(crate) fn fast_lane_applies(env: &Env, config: &MultisigConfig, transaction: &Transaction) -> bool {
+        let FastLane::Enabled(policy) = &config.fast_lane else {
+            return false;
+        };
+        if transaction.kind != TransactionKind::Transfer
+            || transaction.token != policy.token
+            || transaction.amount > policy.max_amount
+        {
+            return false;
+        }
+
+        let spent = Self::get_fast_lane_spent_today(env.clone());
+        spent.checked_add(transaction.amount).is_some_and(|total| total <= policy.daily_limit)
+    }
+
+    pub(crate) fn record_fast_lane_spend(env: &Env, amount: i128) -> Result<(), MultisigError> {
+        let spent = Self::get_fast_lane_spent_today(env.clone())
+            .checked_add(amount)
+            .ok_or(MultisigError::ArithmeticError)?;
+        env.storage().persistent().set(&(FAST_LANE_KEY, Self::current_period(env)), &spent);
+
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Turns the fast lane off. Any single owner may do this; turning it back
+    /// on needs a config proposal.
+    pub fn disable_fast_lane(env: Env, caller: Address) -> Result<(), MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        let mut config = Self::get_config(&env)?;
+        config.fast_lane = FastLane::Disabled;
+        env.storage().persistent().set(&CONFIG_KEY, &config);
+
+        Self::publish_event(&env, (symbol_short!("fast_off"),), caller);
+
+        Ok(())
+    }
+
+    pub fn get_fast_lane(env: Env) -> Result<FastLane, MultisigError> {
+        Ok(Self::get_config(&env)?.fast_lane)
+    }
+
+    /// Amount moved through the fast lane during the current wallet period.
+    pub fn get_fast_lane_spent_today(env: Env) -> i128 {
+        env.storage().persistent().get(&(FAST_LANE_KEY, Self::current_period(&env))).unwrap_or(0)
+    }
+}
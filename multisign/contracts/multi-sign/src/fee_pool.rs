@@ -0,0 +1,86 @@
+//! Fee pool: a governed budget for operational costs. The quorum sets part
+//! of the wallet's balance of a token aside, and executor reimbursements and
+//! keeper tips for extending the wallet's TTL are paid from it automatically.
+//! Pooled funds are reserved, so ordinary transfers cannot spend them.
+
+use soroban_sdk::{contractimpl, symbol_short, token, Address, Env, Symbol};
+
+use crate::{
+    ExecutorReimbursement, MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError,
+};
+
+const FEE_POOL_KEY: Symbol = symbol_short!("fee_pool");
+
+impl MultisigContract {
+    /// Sets `amount` of the wallet's unreserved `token` balance aside.
+    pub(crate) fn fund_fee_pool(env: &Env, token: Address, amount: i128) -> Result<(), MultisigError> {
+        if amount <= 0 {
+            return Err(MultisigError::InvalidAmount);
+        }
+        // Unlike transfers, the pool needs the tokens to be there even when
+        // nothing else is reserved yet.
+        let pool = Self::get_fee_pool(env.clone(), token.clone());
+        let reserved = Self::get_bonds_held(env.clone(), token.clone())
+            + Self::get_claims_held(env.clone(), token.clone())
+            + pool;
+        let balance = token::Client::new(env, &token).balance(&env.current_contract_address());
+        if amount > balance - reserved {
+            return Err(MultisigError::BondReserved);
+        }
+
+        let pool = pool.checked_add(amount).ok_or(MultisigError::ArithmeticError)?;
+        env.storage().persistent().set(&(FEE_POOL_KEY, token.clone()), &pool);
+        Self::publish_event(env, (symbol_short!("pool_fund"), token), pool);
+
+        Ok(())
+    }
+
+    /// Returns whatever is left in the pool to the wallet's free balance.
+    pub(crate) fn drain_fee_pool(env: &Env, token: Address) {
+        let pool = Self::get_fee_pool(env.clone(), token.clone());
+        env.storage().persistent().remove(&(FEE_POOL_KEY, token.clone()));
+        Self::publish_event(env, (symbol_short!("pool_drai"), token), pool);
+    }
+
+    /// Pays `amount` of `token` to `to` out of the pool. Returns whether it
+    /// could; an empty or short pool pays nothing.
+    pub(crate) fn pay_from_fee_pool(env: &Env, token: &Address, to: &Address, amount: i128) -> bool {
+        let pool = Self::get_fee_pool(env.clone(), token.clone());
+        if pool < amount || Self::ensure_token_not_paused(env, token).is_err() {
+            return false;
+        }
+        let paid = token::Client::new(env, token)
+            .try_transfer(&env.current_contract_address(), to, &amount)
+            .is_ok();
+        if paid {
+            env.storage().persistent().set(&(FEE_POOL_KEY, token.clone()), &(pool - amount));
+        }
+        paid
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    pub fn get_fee_pool(env: Env, token: Address) -> i128 {
+        env.storage().persistent().get(&(FEE_POOL_KEY, token)).unwrap_or(0)
+    }
+
+    /// Permissionless upkeep: extends the wallet's core entries if they are
+    /// due and tips `keeper` the executor reimbursement from the fee pool for
+    /// doing so. Returns whether anything was extended.
+    pub fn keep_alive(env: Env, keeper: Address) -> Result<bool, MultisigError> {
+        keeper.require_auth();
+        if !Self::core_ttl_due(&env) {
+            return Ok(false);
+        }
+        Self::extend_core_ttl(&env);
+
+        if let ExecutorReimbursement::Enabled(tip) = Self::get_config(&env)?.reimbursement {
+            if Self::pay_from_fee_pool(&env, &tip.token, &keeper, tip.amount) {
+                Self::publish_event(&env, (symbol_short!("keeper"),), (keeper, tip.amount));
+            }
+        }
+
+        Ok(true)
+    }
+}
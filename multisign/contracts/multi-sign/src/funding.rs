@@ -0,0 +1,95 @@
+//! Departmental funding. The quorum may grant another wallet, typically a
+//! child multisig, a standing allowance of one token per wallet period. The
+//! child draws on it with `pull` whenever it needs funds, instead of the
+//! parent approving a transfer each time.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, token, Address, Env, Symbol};
+
+use crate::{MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError};
+
+const PULL_GRANT_KEY: Symbol = symbol_short!("pull_grnt");
+const PULL_USAGE_KEY: Symbol = symbol_short!("pull_use");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PullGrant {
+    pub token: Address,
+    /// Total the child may pull per wallet period.
+    pub per_period: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PullAllowance {
+    Revoked,
+    Granted(PullGrant),
+}
+
+impl MultisigContract {
+    pub(crate) fn set_pull_allowance(
+        env: &Env,
+        child: Address,
+        allowance: PullAllowance,
+    ) -> Result<(), MultisigError> {
+        if child == env.current_contract_address() || Self::is_unusable_address(env, &child) {
+            return Err(MultisigError::InvalidAddress);
+        }
+        let key = (PULL_GRANT_KEY, child.clone());
+        match &allowance {
+            PullAllowance::Granted(grant) if grant.per_period <= 0 => return Err(MultisigError::InvalidAmount),
+            PullAllowance::Granted(grant) if Self::is_unusable_address(env, &grant.token) => {
+                return Err(MultisigError::InvalidToken)
+            },
+            PullAllowance::Granted(grant) => env.storage().persistent().set(&key, grant),
+            PullAllowance::Revoked => env.storage().persistent().remove(&key),
+        }
+        Self::publish_event(env, (symbol_short!("pull_set"), child), allowance);
+
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Sends `amount` of the granted token to `child`, counted against its
+    /// allowance for the current period. Outflow checks that apply to
+    /// transfers, such as lockdown, paused tokens, token policies, savings
+    /// locks and reserved balances, apply here too.
+    pub fn pull(env: Env, child: Address, amount: i128) -> Result<i128, MultisigError> {
+        child.require_auth();
+
+        let grant: PullGrant = env.storage().persistent().get(&(PULL_GRANT_KEY, child.clone()))
+            .ok_or(MultisigError::Unauthorized)?;
+        if amount <= 0 {
+            return Err(MultisigError::InvalidAmount);
+        }
+        let pulled = Self::get_pulled_this_period(env.clone(), child.clone())
+            .checked_add(amount)
+            .ok_or(MultisigError::ArithmeticError)?;
+        if pulled > grant.per_period {
+            return Err(MultisigError::AmountExceedsLimit);
+        }
+
+        Self::ensure_not_locked_down(&env).map_err(|error| Self::multisig_error(&env, error))?;
+        Self::ensure_token_not_paused(&env, &grant.token)?;
+        Self::check_token_policy(&env, &grant.token, &child, amount)?;
+        Self::check_savings_lock(&env, &grant.token, amount, false)?;
+        Self::check_reserved_balance(&env, &grant.token, amount)?;
+
+        env.storage().persistent().set(&(PULL_USAGE_KEY, child.clone(), Self::current_period(&env)), &pulled);
+        token::Client::new(&env, &grant.token).transfer(&env.current_contract_address(), &child, &amount);
+
+        Self::publish_event(&env, (symbol_short!("pull"), child), (grant.token, amount));
+
+        Ok(grant.per_period - pulled)
+    }
+
+    pub fn get_pull_grant(env: Env, child: Address) -> Option<PullGrant> {
+        env.storage().persistent().get(&(PULL_GRANT_KEY, child))
+    }
+
+    /// Amount `child` has pulled during the current wallet period.
+    pub fn get_pulled_this_period(env: Env, child: Address) -> i128 {
+        env.storage().persistent().get(&(PULL_USAGE_KEY, child, Self::current_period(&env))).unwrap_or(0)
+    }
+}
@@ -0,0 +1,49 @@
+//! Single-call health summary for monitoring.
+
+use soroban_sdk::{contractimpl, contracttype, Env};
+
+use crate::{MultisigConfig, MultisigContract, MultisigContractArgs, MultisigContractClient, CONFIG_KEY};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WalletHealth {
+    pub initialized: bool,
+    pub locked_down: bool,
+    pub owner_count: u32,
+    pub required_approvals: u32,
+    pub pending_count: u32,
+    pub epoch: u32,
+    /// Lower bound on the ledgers left before the core entries expire.
+    pub ttl_remaining: u32,
+    /// Whether the active owners can still reach `required_approvals` between them.
+    pub quorum_reachable: bool,
+}
+
+#[contractimpl]
+impl MultisigContract {
+    pub fn health(env: Env) -> WalletHealth {
+        let Some(config) = env.storage().persistent().get::<_, MultisigConfig>(&CONFIG_KEY) else {
+            return WalletHealth {
+                initialized: false,
+                locked_down: false,
+                owner_count: 0,
+                required_approvals: 0,
+                pending_count: 0,
+                epoch: 0,
+                ttl_remaining: 0,
+                quorum_reachable: false,
+            };
+        };
+
+        WalletHealth {
+            initialized: true,
+            locked_down: Self::is_locked_down(env.clone()),
+            owner_count: config.owners.len(),
+            required_approvals: config.required_approvals,
+            pending_count: Self::get_pending_count(env.clone()),
+            epoch: config.epoch,
+            ttl_remaining: Self::core_ttl_remaining(&env),
+            quorum_reachable: Self::quorum_reachable(&env, &config),
+        }
+    }
+}
@@ -0,0 +1,54 @@
+//! Idempotent submission. A front-end may tag a transfer with a key of its
+//! choosing; submitting again under the same key returns the proposal the
+//! first attempt created, so retries after a lost response cannot file the
+//! payment twice. Keys are scoped to the submitting owner.
+
+use soroban_sdk::{contractimpl, symbol_short, Address, BytesN, Env, Symbol};
+
+use crate::{
+    MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError, Transaction,
+    TransactionKind, TX_KEY,
+};
+
+const IDEMPOTENCY_KEY: Symbol = symbol_short!("idem");
+
+#[contractimpl]
+impl MultisigContract {
+    /// Like `submit_transaction`, but returns the id of the proposal already
+    /// filed by `caller` under `idempotency_key` instead of filing another.
+    /// Reusing a key for a different transfer fails with `DuplicateTransaction`.
+    pub fn submit_transaction_idempotent(
+        env: Env,
+        caller: Address,
+        to: Address,
+        amount: i128,
+        token: Address,
+        data: BytesN<32>,
+        idempotency_key: BytesN<32>,
+    ) -> Result<u32, MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        let key = (IDEMPOTENCY_KEY, caller.clone(), idempotency_key);
+        if let Some(existing_id) = env.storage().persistent().get::<_, u32>(&key) {
+            let existing: Option<Transaction> = env.storage().persistent().get(&(TX_KEY, existing_id));
+            if let Some(existing) = existing {
+                if (existing.to, existing.amount, existing.token, existing.data) != (to, amount, token, data) {
+                    return Err(MultisigError::DuplicateTransaction);
+                }
+            }
+            return Ok(existing_id);
+        }
+
+        Self::validate_transaction_inputs(&env, &to, amount, &token)?;
+        let id = Self::store_transaction(&env, &caller, to, amount, token, data, TransactionKind::Transfer)?;
+        env.storage().persistent().set(&key, &id);
+
+        Ok(id)
+    }
+
+    /// The proposal `owner` filed under `idempotency_key`, if any.
+    pub fn get_idempotent_submission(env: Env, owner: Address, idempotency_key: BytesN<32>) -> Option<u32> {
+        env.storage().persistent().get(&(IDEMPOTENCY_KEY, owner, idempotency_key))
+    }
+}
@@ -0,0 +1,141 @@
+//! Storage self-check. `verify_invariants` walks the wallet's critical state
+//! and reports every relation that no longer holds: counters that disagree
+//! with the entries they count, approval tallies that disagree with the
+//! recorded votes, and earmarked balances that disagree with what is set
+//! aside. Monitoring can poll it, and tests run it after each scenario, so
+//! drift introduced by a new feature shows up where it happens.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, token, Address, Env, Map, Symbol, Vec};
+
+use crate::{
+    BondRequirement, ExecutorReimbursement, MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError, Transaction,
+    TransactionStatus, TX_KEY,
+};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvariantViolation {
+    /// Which check failed, e.g. `pending` or `approvals`.
+    pub check: Symbol,
+    /// The proposal it failed for, if it is specific to one.
+    pub transaction_id: Option<u32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvariantReport {
+    pub transactions_checked: u32,
+    pub violations: Vec<InvariantViolation>,
+}
+
+impl InvariantReport {
+    fn flag(&mut self, check: Symbol, transaction_id: Option<u32>) {
+        self.violations.push_back(InvariantViolation { check, transaction_id });
+    }
+}
+
+impl MultisigContract {
+    fn add_to_token(totals: &mut Map<Address, i128>, token: Address, amount: i128) {
+        let total = totals.get(token.clone()).unwrap_or(0);
+        totals.set(token, total.saturating_add(amount));
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Checks the wallet's stored state for consistency. It reads every
+    /// proposal, so it is meant for simulation and tests rather than for
+    /// calls on the ledger.
+    pub fn verify_invariants(env: Env) -> Result<InvariantReport, MultisigError> {
+        let config = Self::get_config(&env)?;
+        let mut report = InvariantReport { transactions_checked: 0, violations: Vec::new(&env) };
+
+        // The signer set can reach its thresholds.
+        let total_weight = config.owners.iter()
+            .map(|owner| Self::owner_weight(&config, &owner))
+            .fold(0u32, u32::saturating_add);
+        let has_duplicates = config.owners.iter().enumerate()
+            .any(|(index, owner)| config.owners.first_index_of(owner) != Some(index as u32));
+        if config.owners.is_empty() || has_duplicates {
+            report.flag(symbol_short!("owners"), None);
+        }
+        if config.required_approvals == 0 || config.required_approvals > Self::voting_weight(&config) {
+            report.flag(symbol_short!("threshold"), None);
+        }
+
+        let mut pending = Vec::new(&env);
+        let mut bonds = Map::new(&env);
+        let mut claims = Map::new(&env);
+        for transaction_id in 1..=Self::transaction_count(&env) {
+            let Some(transaction) = env.storage().persistent().get::<_, Transaction>(&(TX_KEY, transaction_id)) else {
+                if !Self::is_archived(&env, transaction_id) {
+                    report.flag(symbol_short!("tx_count"), Some(transaction_id));
+                }
+                continue;
+            };
+            report.transactions_checked += 1;
+
+            if transaction.status == TransactionStatus::Pending {
+                pending.push_back(transaction_id);
+
+                // Every recorded approval by a seated owner is in the tally,
+                // and the tally is no more than the seats could give.
+                let recorded = Self::approvers(&env, &config, transaction_id).iter()
+                    .map(|owner| Self::owner_weight(&config, &owner))
+                    .fold(0u32, u32::saturating_add);
+                if recorded > transaction.approvals || transaction.approvals > total_weight {
+                    report.flag(symbol_short!("approvals"), Some(transaction_id));
+                }
+            }
+
+            if let Some(bond) = Self::get_bond(env.clone(), transaction_id) {
+                Self::add_to_token(&mut bonds, bond.token, bond.amount);
+            }
+            if let Some(entry) = Self::get_claimable(env.clone(), transaction_id) {
+                Self::add_to_token(&mut claims, entry.token, entry.amount);
+            }
+        }
+        if pending.len() != Self::get_pending_count(env.clone()) {
+            report.flag(symbol_short!("pending"), None);
+        }
+        if pending != Self::get_pending_transactions(env.clone()) {
+            report.flag(symbol_short!("pend_ids"), None);
+        }
+
+        // What is earmarked matches the entries it is held for, and the
+        // wallet still holds it.
+        let mut tokens = Vec::new(&env);
+        for token in bonds.keys().iter().chain(claims.keys().iter()) {
+            if !tokens.contains(&token) {
+                tokens.push_back(token);
+            }
+        }
+        if let BondRequirement::Required(bond) = &config.bond {
+            if !tokens.contains(&bond.token) {
+                tokens.push_back(bond.token.clone());
+            }
+        }
+        if let ExecutorReimbursement::Enabled(reimbursement) = &config.reimbursement {
+            if !tokens.contains(&reimbursement.token) {
+                tokens.push_back(reimbursement.token.clone());
+            }
+        }
+        for token in tokens.iter() {
+            let bonds_held = Self::get_bonds_held(env.clone(), token.clone());
+            let claims_held = Self::get_claims_held(env.clone(), token.clone());
+            if bonds_held != bonds.get(token.clone()).unwrap_or(0) {
+                report.flag(symbol_short!("bond_held"), None);
+            }
+            if claims_held != claims.get(token.clone()).unwrap_or(0) {
+                report.flag(symbol_short!("claim_hld"), None);
+            }
+            let pooled = Self::get_fee_pool(env.clone(), token.clone());
+            let balance = token::Client::new(&env, &token).balance(&env.current_contract_address());
+            if bonds_held.saturating_add(claims_held).saturating_add(pooled) > balance {
+                report.flag(symbol_short!("earmark"), None);
+            }
+        }
+
+        Ok(report)
+    }
+}
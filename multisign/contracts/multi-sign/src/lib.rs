@@ -1,10 +1,115 @@
 #![no_std]
 
+mod allowance;
+mod amm;
+mod approval_cap;
+mod archive;
+mod auditor;
+mod batch;
+mod bond;
+mod bridge;
+mod certificate;
+mod checklist;
+mod claim;
+mod config_schedule;
+mod contract_call;
+mod dca;
+mod diagnostics;
+mod document;
+mod drafts;
+mod drill;
+mod event_label;
+mod execution;
+mod fast_lane;
+mod fee_pool;
+mod funding;
+mod health;
+mod idempotency;
+mod invariants;
+mod listener;
+mod listing;
+mod lockdown;
+mod migration;
+mod multicall;
+mod native;
+mod oracle;
+mod ordering;
+mod owners;
+mod passkey;
+mod pause;
+mod payment_request;
+mod periods;
+mod policy_signer;
+mod preview;
+mod preauth;
+mod priority;
+mod quorum;
+mod receipt;
+mod recovery;
+mod registry;
+mod relay;
+mod reimburse;
+mod screening;
+mod signers;
+mod simulation;
+mod slate;
+mod spending_limit;
+mod timelock;
+mod token_pause;
+mod ttl;
+mod upgrade;
 mod test;
 
+pub use allowance::AllowanceGrant;
+pub use archive::ArchivedTransaction;
+pub use auditor::{AuditorAccess, AuditorGrant};
+pub use amm::{AddLiquidity, AssetBalance, LiquidityAction, LiquidityPool, LiquidityPoolClient, RemoveLiquidity};
+pub use batch::{Operation, TokenTransfer};
+pub use bond::{BondRequirement, ProposalBond};
+pub use bridge::{Bridge, BridgeClient, BridgeMessage};
+pub use certificate::QuorumCertificate;
+pub use checklist::{Attestation, ChecklistItem};
+pub use claim::ClaimableEntry;
+pub use contract_call::ContractCall;
+pub use dca::{DcaPlan, SwapRouter, SwapRouterClient};
+pub use diagnostics::ErrorInfo;
+pub use document::DocumentSignature;
+pub use drill::DrillRecord;
+pub use fast_lane::{FastLane, FastLanePolicy};
+pub use funding::{PullAllowance, PullGrant};
+pub use health::WalletHealth;
+pub use listener::{ProposalListener, ProposalListenerClient, ProposalUpdate};
+pub use listing::TxFilter;
+pub use invariants::{InvariantReport, InvariantViolation};
+pub use lockdown::LockdownState;
+pub use migration::{ExportedTransaction, StateExport};
+pub use multicall::OwnerAction;
+pub use oracle::{OracleAsset, PriceData, PriceOracle, PriceOracleClient};
+pub use owners::{OwnerInit, OwnerReplacement, OwnerRole};
+pub use passkey::{KeyLossClaim, PasskeyEnrollment};
+pub use payment_request::PaymentRequest;
+pub use periods::{PeriodCalendar, PeriodWindow};
+pub use policy_signer::{PolicyCosigner, PolicyCosignerClient};
+pub use preview::ConfigImpact;
+pub use priority::{Priority, UrgentHandling, UrgentPolicy};
+pub use receipt::Receipt;
+pub use recovery::{RecoveryContact, RecoveryRehearsal};
+pub use registry::{PortfolioEntry, PortfolioValue, RegisteredToken, TokenValue};
+pub use relay::SignedApproval;
+pub use reimburse::{ExecutorReimbursement, Reimbursement};
+pub use screening::{AddressScreener, AddressScreenerClient, ScreeningRecord};
+pub use signers::{SignerKey, SignerPolicy, SignerPolicyClient, SignerProof};
+pub use simulation::{BalanceDelta, SimulationLock};
+pub use slate::Slate;
+pub use spending_limit::{SpendingLimit, SpendingPolicy};
+pub use timelock::{Timelock, TimelockPolicy};
+pub use token_pause::TokenPause;
+pub use ttl::StorageTtl;
+
 use soroban_sdk::{
-    contract, contractimpl, contracterror, contracttype, contractmeta,
-    symbol_short, Address, BytesN, Env, Vec, Symbol, Map, token
+    auth::{Context, CustomAccountInterface}, contract, contractimpl, contracterror,
+    contracttype, contractmeta, crypto::Hash, symbol_short, xdr::ToXdr, Address,
+    Bytes, BytesN, Env, Error, IntoVal, String, TryFromVal, Vec, Symbol, Map, token
 };
 
 contractmeta!(
@@ -29,11 +134,416 @@ pub enum MultisigError {
     InvalidAmount = 11,
     InvalidAddress = 12,
     TokenTransferFailed = 13,
+    TransactionCancelled = 14,
+    AlreadyRejected = 15,
+    InsufficientRejections = 16,
+    TemplateNotFound = 17,
+    IncompleteTemplate = 18,
+    DuplicateTransaction = 19,
+    TransactionExpired = 20,
+    VotesPruned = 21,
+    AccountModeDisabled = 22,
+    InsufficientSignatures = 23,
+    DuplicateSigner = 24,
+    AmountExceedsLimit = 25,
+    RecipientNotAllowed = 26,
+    InvalidMetadata = 27,
+    SubmitterCannotExecute = 28,
+    SavingsLocked = 29,
+    RecurringNotFound = 30,
+    PaymentNotDue = 31,
+    PriceUnavailable = 32,
+    StalePrice = 33,
+    InvalidSchedule = 34,
+    DcaPlanNotFound = 35,
+    DcaPlanCompleted = 36,
+    SlippageExceeded = 37,
+    InsufficientLiquidity = 38,
+    BridgeNotConfigured = 39,
+    DocumentAlreadySigned = 40,
+    DocumentNotSigned = 41,
+    AmountImplausible = 42,
+    InvalidToken = 43,
+    BondReserved = 44,
+    LockedDown = 45,
+    NotLockedDown = 46,
+    RecoveryNotConfigured = 47,
+    RecoveryNotRequested = 48,
+    OwnerStillActive = 49,
+    SimulationMismatch = 50,
+}
+
+/// Errors added after `MultisigError` reached the 50 cases a contract error
+/// enum may declare. Codes start at 100, so the two never overlap. Entry
+/// points still declare `MultisigError`, and fail with these by aborting.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ExtendedError {
+    /// The proposal's execution timelock has not run out.
+    EarlyExecution = 100,
+    /// A scheduled config change is executed before its ledger.
+    ChangeNotEffective = 101,
+    /// Strict ordering holds the proposal behind an earlier open one.
+    OutOfOrder = 102,
+    /// A batch is empty, too long, or reached execution unexpanded.
+    InvalidBatch = 103,
+    TooManyIds = 104,
+    SlateNameTaken = 105,
+    DuplicateSlateEntry = 106,
+    /// The wallet is paused by its circuit breaker.
+    Paused = 107,
 }
 
 #[contract]
 pub struct MultisigContract;
 
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TransactionStatus {
+    /// Editable by its submitter and not yet open for votes.
+    Draft,
+    Pending,
+    Executed,
+    Cancelled,
+    Expired,
+}
+
+/// A reusable transfer skeleton. Any field left as `None` is a slot that must be
+/// filled by the overrides passed to `submit_from_template`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferTemplate {
+    pub to: Option<Address>,
+    pub amount: Option<i128>,
+    pub token: Option<Address>,
+    pub data: Option<BytesN<32>>,
+}
+
+/// What to do when a transfer identical to a pending one is submitted.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DuplicatePolicy {
+    Allow,
+    Warn,
+    Reject,
+}
+
+/// Why an owner voted to reject a proposal.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RejectionReason {
+    Duplicate,
+    WrongAmount,
+    WrongRecipient,
+    PolicyViolation,
+    Other,
+}
+
+/// Governs how much storage is kept for proposals once they are closed. Pruned
+/// proposals drop their vote lists, keeping only a digest.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RetentionPolicy {
+    KeepForever,
+    /// Cancelled and expired proposals are pruned as soon as they close.
+    PruneClosed,
+    /// Closed proposals outside the N most recent ids are pruned.
+    KeepLatest(u32),
+    /// Closed proposals are pruned once closed for this many ledgers.
+    KeepForLedgers(u32),
+}
+
+/// Signatures required in `__check_auth`, by the kind of operation being
+/// authorized. An auth entry covering several contexts needs the highest
+/// applicable threshold.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountAuthPolicy {
+    /// `transfer`, `transfer_from` and `approve` calls on any contract.
+    pub token_transfer: u32,
+    /// Every other contract invocation, including admin calls.
+    pub contract_call: u32,
+    /// Contract deployments made on behalf of the wallet.
+    pub create_contract: u32,
+}
+
+/// Outflow rules for one token, enforced on proposals and on `__check_auth` alike.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenPolicy {
+    /// Largest amount a single transfer may move, if capped.
+    pub max_amount: Option<i128>,
+    /// Permitted recipients; empty means any recipient.
+    pub allowed_recipients: Vec<Address>,
+}
+
+/// Reduced approval requirement for routine transfers to a known address.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TrustedRecipient {
+    pub threshold: u32,
+    /// Transfers above this amount fall back to the wallet threshold.
+    pub max_amount: i128,
+}
+
+/// Savings sub-balance of one token: `amount` of the wallet's balance may not
+/// be spent before `unlock_ledger`, whatever the approvals, unless every owner
+/// signs off.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SavingsLock {
+    pub amount: i128,
+    pub unlock_ledger: u32,
+}
+
+/// Amount to pay expressed in the oracle's base asset (e.g. USD), scaled to the
+/// paid token's decimals, and converted at the latest oracle price.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IndexedAmount {
+    pub oracle: Address,
+    pub value: i128,
+    /// Oldest acceptable price observation, in seconds.
+    pub max_price_age: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PaymentAmount {
+    Fixed(i128),
+    Indexed(IndexedAmount),
+}
+
+/// A quorum-approved standing payment; each installment can be triggered by
+/// anyone once due.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecurringPayment {
+    pub to: Address,
+    pub token: Address,
+    pub amount: PaymentAmount,
+    /// Ledgers between installments.
+    pub interval: u32,
+    pub next_due: u32,
+    pub payments_made: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AccountMode {
+    /// `__check_auth` rejects everything; the wallet only acts through proposals.
+    Disabled,
+    Enabled(AccountAuthPolicy),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConfigChange {
+    /// Seats a new owner with one vote and every role.
+    AddOwner(Address),
+    /// Removes an owner's seat and withdraws their pending approvals.
+    RemoveOwner(Address),
+    /// Sum of approval weights a proposal needs, at most the owners' total.
+    SetThreshold(u32),
+    /// Reject votes needed before any owner may cancel another's proposal.
+    SetCancelThreshold(u32),
+    SaveTemplate(Symbol, TransferTemplate),
+    RemoveTemplate(Symbol),
+    SetDuplicatePolicy(DuplicatePolicy),
+    /// Number of ledgers a new proposal stays open; zero disables expiry.
+    SetProposalLifetime(u32),
+    SetRetentionPolicy(RetentionPolicy),
+    SetAccountMode(AccountMode),
+    SetTokenPolicy(Address, TokenPolicy),
+    RemoveTokenPolicy(Address),
+    /// Overrides the threshold for proposals moving a token, higher or lower
+    /// than the wallet's; `None` removes the override.
+    SetTokenThreshold(Address, Option<u32>),
+    /// When enabled, the submitter of a proposal may not be the one to execute it.
+    SetDualControl(bool),
+    /// When enabled, proposals execute strictly in the order they were submitted.
+    SetStrictOrdering(bool),
+    /// Expedited threshold and per-owner cap for urgent transfers. Disabling
+    /// it stops new urgent submissions and makes open ones normal again.
+    SetUrgentHandling(UrgentHandling),
+    /// Whether submitting a proposal also counts as the submitter's approval.
+    SetSubmitterAutoApprove(bool),
+    /// Lowers the threshold for transfers of a token to a recipient, up to a cap.
+    SetTrustedRecipient(Address, Address, TrustedRecipient),
+    RemoveTrustedRecipient(Address, Address),
+    /// Admits or removes an external address allowed to file payment requests.
+    SetRequester(Address, bool),
+    /// Reserves part of the wallet's balance of a token until a ledger. While a
+    /// lock is active, reducing or shortening it needs unanimous approval.
+    SetSavingsLock(Address, SavingsLock),
+    CreateRecurring(RecurringPayment),
+    CancelRecurring(u32),
+    CreateDcaPlan(DcaPlan),
+    CancelDcaPlan(u32),
+    SetBridge(Option<Address>),
+    /// Contract told about every proposal status change; `None` removes it.
+    SetListener(Option<Address>),
+    /// Contract whose approval counts as one more approval; `None` removes it.
+    SetPolicySigner(Option<Address>),
+    /// Contract that vets payment recipients at submission and execution;
+    /// `None` removes it.
+    SetScreener(Option<Address>),
+    /// Rejects transfers of more than `10^x` whole units of any token, judged by
+    /// the token's `decimals()`; `None` disables the check.
+    SetMaxWholeUnits(Option<u32>),
+    SetProposalBond(BondRequirement),
+    SetExecutorReimbursement(ExecutorReimbursement),
+    SetFastLane(FastLane),
+    /// Length and offset of the periods per-period limits count against.
+    SetPeriodCalendar(PeriodCalendar),
+    /// Delay between reaching quorum and execution for proposals of at least
+    /// an amount.
+    SetTimelock(Timelock),
+    /// Ledgers between an approved unlock and execution resuming.
+    SetLockdownCooldown(u32),
+    /// The only proposal that can execute during a lockdown.
+    LiftLockdown,
+    /// Hands an inactive owner's seat to the recovery contact that requested it.
+    AcknowledgeRecovery(Address),
+    /// Label leading the topics of every event the wallet publishes; `None`
+    /// removes it.
+    SetEventLabel(Option<Symbol>),
+    /// Zeroes every allowance the wallet has granted.
+    RevokeAllAllowances,
+    /// Grants or revokes another wallet's standing allowance to `pull` a
+    /// token, reset every wallet period.
+    SetPullAllowance(Address, PullAllowance),
+    /// Lets the wallet satisfy, once and until the given ledger, the auth entry
+    /// whose signature payload has this hash, without owner signatures.
+    PreAuthorize(BytesN<32>, u32),
+    RevokePreAuthorization(BytesN<32>),
+    /// Ledgers without activity after which an owner stops counting towards
+    /// quorum reachability; zero disables the check.
+    SetInactivityPeriod(u32),
+    /// Sets a threshold the active owners can reach. Only valid while the
+    /// quorum alarm is raised, and needs only the active owners' approvals.
+    RecoverQuorum(u32),
+    /// Resumes outflows of a token halted with `pause_token`.
+    UnpauseToken(Address),
+    /// Adds a token contract to the registry, caching its metadata.
+    RegisterToken(Address),
+    DeregisterToken(Address),
+    /// Replaces the attestations every proposal of a kind needs before its
+    /// approvals count; an empty list removes the requirement.
+    SetChecklist(ProposalKind, Vec<ChecklistItem>),
+    /// Changes added after `ConfigChange` reached the 50 cases a contract
+    /// enum may declare.
+    Extended(ExtendedChange),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ExtendedChange {
+    /// Sets or removes a token's daily allowance on a reduced threshold.
+    SetSpendingLimit(Address, SpendingLimit),
+    /// Sets part of the wallet's free balance of a token aside for fees.
+    FundFeePool(Address, i128),
+    /// Releases a token's fee pool back to the free balance.
+    DrainFeePool(Address),
+    /// Grants or revokes an auditor's read access to detail views.
+    SetAuditor(Address, AuditorAccess),
+    /// Gives an owner's seat a new vote weight of at least 1.
+    SetOwnerWeight(Address, u32),
+    /// Restricts an owner's seat to a role, or gives it every role again.
+    SetOwnerRole(Address, OwnerRole),
+    /// Completes a recovery rehearsal for an owner's seat without moving it.
+    RehearseRecovery(Address),
+    /// Names the owners who may pause the wallet; empty lets every owner.
+    SetPausers(Vec<Address>),
+    /// Lifts a pause.
+    Unpause,
+    /// Hands an owner's seat to a new key.
+    ReplaceOwner(OwnerReplacement),
+    /// Swaps the wallet's code for an uploaded wasm, keeping its storage.
+    Upgrade(BytesN<32>),
+    /// Changes when and how far storage entries are extended.
+    SetStorageTtl(StorageTtl),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum TransactionKind {
+    /// Moves `amount` of `token` to `to`.
+    Transfer,
+    /// Applies a wallet configuration change once approved; `to`, `amount`
+    /// and `token` are unused.
+    Config(ConfigChange),
+    /// Adds or removes liquidity; `to` and `token` hold the pool address.
+    Liquidity(LiquidityAction),
+    /// Sends a cross-chain message; `to` and `token` hold the bridge address.
+    BridgeMessage(BridgeMessage),
+    /// Attests a document hash, which is also stored in `data`.
+    SignDocument(BytesN<32>),
+    Approve(AllowanceGrant),
+    /// Sets `amount` of `token` aside for `to` to claim within this many
+    /// ledgers, instead of pushing it to them.
+    Claimable(u32),
+    /// Deploys the Stellar Asset Contract for a classic asset, given as its
+    /// XDR-encoded `Asset`, and adds it to the token registry; `to` and
+    /// `token` hold the SAC address it will have.
+    WrapAsset(Bytes),
+    /// Rehearses the approval flow without side effects beyond recording
+    /// who took part; `to` and `token` hold the wallet itself.
+    Drill,
+    /// Invokes a function of another contract as the wallet; `to` and `token`
+    /// hold the target contract.
+    ContractCall(ContractCall),
+    /// Runs its operations in order, all or none; `to` and `token` hold the
+    /// wallet itself.
+    Batch(Vec<Operation>),
+}
+
+/// Payload-free tag of a `TransactionKind`, for rules that apply per kind.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProposalKind {
+    Transfer,
+    Config,
+    Liquidity,
+    BridgeMessage,
+    SignDocument,
+    Approve,
+    Claimable,
+    WrapAsset,
+    Drill,
+    ContractCall,
+    Batch,
+}
+
+impl TransactionKind {
+    pub fn proposal_kind(&self) -> ProposalKind {
+        match self {
+            TransactionKind::Transfer => ProposalKind::Transfer,
+            TransactionKind::Config(_) => ProposalKind::Config,
+            TransactionKind::Liquidity(_) => ProposalKind::Liquidity,
+            TransactionKind::BridgeMessage(_) => ProposalKind::BridgeMessage,
+            TransactionKind::SignDocument(_) => ProposalKind::SignDocument,
+            TransactionKind::Approve(_) => ProposalKind::Approve,
+            TransactionKind::Claimable(_) => ProposalKind::Claimable,
+            TransactionKind::WrapAsset(_) => ProposalKind::WrapAsset,
+            TransactionKind::Drill => ProposalKind::Drill,
+            TransactionKind::ContractCall(_) => ProposalKind::ContractCall,
+            TransactionKind::Batch(_) => ProposalKind::Batch,
+        }
+    }
+}
+
+/// One owner's approval of a proposal.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApprovalRecord {
+    pub approved_at: u32,
+    /// `proposal_hash` of the proposal as it stood when approved.
+    pub content_hash: BytesN<32>,
+}
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct Transaction {
@@ -41,69 +551,276 @@ pub struct Transaction {
     pub amount: i128,
     pub token: Address,
     pub data: BytesN<32>,
-    pub executed: bool,
+    pub kind: TransactionKind,
+    pub status: TransactionStatus,
     pub approvals: u32,
+    pub rejections: u32,
     pub submitter: Address,
+    /// Last ledger sequence at which the proposal may still be approved or executed.
+    pub expires_at: Option<u32>,
+    /// SHA-256 of the XDR-encoded `(approvals, rejections)` lists, set once they are pruned.
+    pub votes_digest: Option<BytesN<32>>,
+    /// Ledger at which the proposal was executed, cancelled or expired.
+    pub closed_at: Option<u32>,
+    pub priority: Priority,
+    /// Ledger at which the proposal last reached quorum; cleared if approvals
+    /// fall below it again.
+    pub approved_at: Option<u32>,
 }
 
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct MultisigConfig {
     pub owners: Vec<Address>,
+    /// Vote weight of owners who carry more than one vote.
+    pub weights: Map<Address, u32>,
+    /// Role of owners restricted to a subset of actions.
+    pub roles: Map<Address, OwnerRole>,
+    /// Sum of approval weights a proposal needs.
+    pub required_approvals: u32,
+    /// Reject votes needed before any owner may cancel a proposal they did not submit.
+    pub cancel_threshold: u32,
+    pub duplicate_policy: DuplicatePolicy,
+    pub proposal_lifetime: u32,
+    pub retention_policy: RetentionPolicy,
+    pub account_mode: AccountMode,
+    pub dual_control: bool,
+    pub submitter_auto_approve: bool,
+    /// Messaging contract used by bridge message proposals.
+    pub bridge: Option<Address>,
+    /// Sanity bound on transfer amounts, as a power of ten of whole token units.
+    pub max_whole_units: Option<u32>,
+    /// Bond each submitter locks until their proposal is settled.
+    pub bond: BondRequirement,
+    /// Paid to the owner who executes a proposal.
+    pub reimbursement: ExecutorReimbursement,
+    pub fast_lane: FastLane,
+    pub lockdown_cooldown: u32,
+    pub inactivity_period: u32,
+    /// Bumped whenever the signer set or a threshold changes.
+    pub epoch: u32,
+}
+
+/// Non-sensitive description of the key backing an owner seat.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignerMetadata {
+    /// e.g. `ed25519`, `secp256r1`, `contract`.
+    pub key_type: Symbol,
+    pub device_label: String,
+    pub registered_at: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OwnerMetadata {
+    Unregistered,
+    Registered(SignerMetadata),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OwnerDetail {
+    pub address: Address,
+    pub metadata: OwnerMetadata,
+}
+
+/// Everything another contract needs to mirror or verify who can act for the wallet.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernanceInfo {
+    pub owners: Vec<Address>,
+    /// Vote weight of each owner, in the order of `owners`.
+    pub weights: Vec<u32>,
     pub required_approvals: u32,
-    pub transaction_count: u32,
+    pub cancel_threshold: u32,
+    pub epoch: u32,
 }
 
-const CONFIG_KEY: Symbol = symbol_short!("config");
-const TX_KEY: Symbol = symbol_short!("tx");
-const APPROVAL_KEY: Symbol = symbol_short!("approval");
+const MAX_DEVICE_LABEL_LEN: u32 = 64;
+
+pub(crate) const CONFIG_KEY: Symbol = symbol_short!("config");
+pub(crate) const TX_KEY: Symbol = symbol_short!("tx");
+pub(crate) const APPROVAL_KEY: Symbol = symbol_short!("approval");
+pub(crate) const REJECTION_KEY: Symbol = symbol_short!("reject");
+pub(crate) const REJECTION_REASON_KEY: Symbol = symbol_short!("rej_why");
+const TEMPLATE_KEY: Symbol = symbol_short!("template");
+pub(crate) const CONTENT_KEY: Symbol = symbol_short!("content");
+const TOKEN_POLICY_KEY: Symbol = symbol_short!("tok_pol");
+const TOKEN_THRESHOLD_KEY: Symbol = symbol_short!("tok_thr");
+pub(crate) const SIGNER_META_KEY: Symbol = symbol_short!("signer_md");
+const TRUSTED_KEY: Symbol = symbol_short!("trusted");
+const SAVINGS_KEY: Symbol = symbol_short!("savings");
+const RECURRING_KEY: Symbol = symbol_short!("recurring");
+pub(crate) const RECURRING_COUNT_KEY: Symbol = symbol_short!("recur_cnt");
+const DECIMALS_KEY: Symbol = symbol_short!("decimals");
+// Counters touched by every submission live in instance storage, apart from the config.
+pub(crate) const TX_COUNT_KEY: Symbol = symbol_short!("tx_count");
+pub(crate) const PENDING_COUNT_KEY: Symbol = symbol_short!("pending");
+pub(crate) const PENDING_INDEX_KEY: Symbol = symbol_short!("pend_ids");
+
+/// About a day of ledgers.
+const DEFAULT_LOCKDOWN_COOLDOWN: u32 = 17_280;
+
+/// All-zero account and contract strkeys, which no one controls.
+const ZERO_ACCOUNT: &str = "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF";
+const ZERO_CONTRACT: &str = "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABSC4";
 
 #[contractimpl]
 impl MultisigContract {
+    /// Runs once at deployment. A factory moving a wallet here names itself
+    /// as `importer`, reserving the wallet for `import_state`; any other
+    /// deployment passes `None` and calls `initialize`.
+    pub fn __constructor(env: Env, importer: Option<Address>) {
+        if let Some(importer) = importer {
+            Self::reserve_for_import(&env, &importer);
+        }
+    }
 
     pub fn initialize(
         env: Env,
         owners: Vec<Address>,
         required_approvals: u32,
+    ) -> Result<(), MultisigError> {
+        let weights = Map::new(&env);
+        let roles = Map::new(&env);
+        Self::setup_wallet(&env, owners, weights, roles, required_approvals)
+    }
+
+    fn setup_wallet(
+        env: &Env,
+        owners: Vec<Address>,
+        weights: Map<Address, u32>,
+        roles: Map<Address, OwnerRole>,
+        required_approvals: u32,
     ) -> Result<(), MultisigError> {
         if env.storage().persistent().has(&CONFIG_KEY) {
             return Err(MultisigError::AlreadyInitialized);
         }
+        if Self::awaits_import(env) {
+            return Err(MultisigError::Unauthorized);
+        }
+
+        let config = MultisigConfig {
+            owners: owners.clone(),
+            weights,
+            roles,
+            required_approvals,
+            cancel_threshold: required_approvals,
+            duplicate_policy: DuplicatePolicy::Warn,
+            proposal_lifetime: 0,
+            retention_policy: RetentionPolicy::KeepForever,
+            account_mode: AccountMode::Disabled,
+            dual_control: false,
+            submitter_auto_approve: true,
+            bridge: None,
+            max_whole_units: None,
+            bond: BondRequirement::Disabled,
+            reimbursement: ExecutorReimbursement::Disabled,
+            fast_lane: FastLane::Disabled,
+            lockdown_cooldown: DEFAULT_LOCKDOWN_COOLDOWN,
+            inactivity_period: 0,
+            epoch: 0,
+        };
+        Self::validate_owner_set(env, &config)?;
+
+        env.storage().persistent().set(&CONFIG_KEY, &config);
+        Self::extend_core_ttl(env);
+        for owner in owners.iter() {
+            Self::record_activity(env, &owner);
+        }
+        
+        Self::publish_event(env, (symbol_short!("init"), owners.len(), required_approvals), ());
+        
+        Ok(())
+    }
 
+    /// Checks the seats of a new config: distinct usable owners, weights of at
+    /// least 1, and a threshold the voting seats can reach.
+    fn validate_owner_set(env: &Env, config: &MultisigConfig) -> Result<(), MultisigError> {
+        let owners = &config.owners;
         if owners.is_empty() {
             return Err(MultisigError::InvalidOwner);
         }
-        
-        if required_approvals == 0 || required_approvals > owners.len() as u32 {
-            return Err(MultisigError::InvalidThreshold);
+        for (seat, weight) in config.weights.iter() {
+            if !owners.contains(&seat) {
+                return Err(MultisigError::InvalidOwner);
+            }
+            if weight == 0 {
+                return Err(MultisigError::InvalidThreshold);
+            }
+        }
+        for seat in config.roles.keys().iter() {
+            if !owners.contains(&seat) {
+                return Err(MultisigError::InvalidOwner);
+            }
         }
+        if !owners.iter().any(|owner| Self::owner_role(config, &owner).can_propose())
+            || !owners.iter().any(|owner| Self::owner_role(config, &owner).can_execute())
+        {
+            return Err(MultisigError::InvalidOwner);
+        }
+        
+        Self::validate_threshold(config, config.required_approvals)?;
 
-        let mut seen = Map::new(&env);
+        let mut seen = Map::new(env);
         for owner in owners.iter() {
+            if Self::is_unusable_address(env, &owner) {
+                return Err(MultisigError::InvalidAddress);
+            }
             if seen.contains_key(owner.clone()) {
                 return Err(MultisigError::DuplicateOwner);
             }
             seen.set(owner.clone(), true);
         }
 
-        let config = MultisigConfig {
-            owners: owners.clone(),
-            required_approvals,
-            transaction_count: 0,
-        };
+        Ok(())
+    }
 
-        env.storage().persistent().set(&CONFIG_KEY, &config);
-        
-        env.events().publish((symbol_short!("init"), owners.len(), required_approvals), ());
+    /// `error` as a `MultisigError`. An `ExtendedError` cannot be returned
+    /// through entry points declaring `MultisigError`, so it aborts the call.
+    pub(crate) fn multisig_error(env: &Env, error: Error) -> MultisigError {
+        MultisigError::try_from(error).unwrap_or_else(|error| env.panic_with_error(error))
+    }
+
+    pub(crate) fn transaction_count(env: &Env) -> u32 {
+        env.storage().instance().get(&TX_COUNT_KEY).unwrap_or(0)
+    }
+
+    /// Counts `transaction_id` in or out of the pending proposals, keeping
+    /// the id index in order.
+    pub(crate) fn track_pending(env: &Env, transaction_id: u32, pending: bool) {
+        let change = if pending { 1 } else { -1 };
+        let count = Self::get_pending_count(env.clone()).saturating_add_signed(change);
+        env.storage().instance().set(&PENDING_COUNT_KEY, &count);
+
+        let mut ids = Self::get_pending_transactions(env.clone());
+        let position = ids.iter().position(|id| id >= transaction_id).unwrap_or(ids.len() as usize) as u32;
+        let present = ids.get(position) == Some(transaction_id);
+        match (pending, present) {
+            (true, false) => ids.insert(position, transaction_id),
+            (false, true) => {
+                ids.remove(position);
+            },
+            _ => return,
+        }
+        env.storage().persistent().set(&PENDING_INDEX_KEY, &ids);
+    }
+
+    fn verify_owner(env: &Env, caller: &Address) -> Result<(), MultisigError> {
+        Self::ensure_owner(env, caller)?;
+        Self::record_activity(env, caller);
         
         Ok(())
     }
 
-    fn verify_owner(env: &Env, caller: &Address) -> Result<(), MultisigError> {
+    /// Checks that `address` holds a seat, without counting as activity by
+    /// it. For paths the owner has not signed, or that only read.
+    fn ensure_owner(env: &Env, address: &Address) -> Result<(), MultisigError> {
         let config: MultisigConfig = env.storage().persistent().get(&CONFIG_KEY)
             .ok_or(MultisigError::Unauthorized)?;
         
-        if !config.owners.contains(caller) {
+        if !config.owners.contains(address) {
             return Err(MultisigError::Unauthorized);
         }
         
@@ -111,169 +828,1326 @@ impl MultisigContract {
     }
 
     fn get_config(env: &Env) -> Result<MultisigConfig, MultisigError> {
-        env.storage().persistent().get(&CONFIG_KEY)
-            .ok_or(MultisigError::Unauthorized)
+        let config = env.storage().persistent().get(&CONFIG_KEY)
+            .ok_or(MultisigError::Unauthorized)?;
+        Self::extend_core_ttl(env);
+
+        Ok(config)
     }
 
-    fn validate_transaction_inputs(
-        _to: &Address,
-        amount: i128,
-        _token: &Address,
-    ) -> Result<(), MultisigError> {
-        if amount <= 0 {
-            return Err(MultisigError::InvalidAmount);
+    fn is_overdue(env: &Env, transaction: &Transaction) -> bool {
+        match transaction.expires_at {
+            Some(expires_at) => env.ledger().sequence() > expires_at,
+            None => false,
         }
-        
-        Ok(())
     }
 
- 
-    pub fn submit_transaction(
-        env: Env,
-        caller: Address,
-        to: Address,
-        amount: i128,
-        token: Address,
-        data: BytesN<32>,
-    ) -> Result<u32, MultisigError> {
-        caller.require_auth();
-        Self::verify_owner(&env, &caller)?;
-        
-        Self::validate_transaction_inputs(&to, amount, &token)?;
-        
-        let mut config = Self::get_config(&env)?;
-        
-        let new_count = config.transaction_count.checked_add(1)
-            .ok_or(MultisigError::ArithmeticError)?;
-        
-        let transaction = Transaction {
-            to: to.clone(),
-            amount,
-            token: token.clone(),
-            data,
-            executed: false,
-            approvals: 1, 
-            submitter: caller.clone(),
-        };
-
-        config.transaction_count = new_count;
-        env.storage().persistent().set(&CONFIG_KEY, &config);
-        
-        env.storage().persistent().set(&(TX_KEY, new_count), &transaction);
-        
-        let mut approvals = Vec::new(&env);
-        approvals.push_back(caller.clone());
-        env.storage().persistent().set(&(APPROVAL_KEY, new_count), &approvals);
+    fn ensure_pending(env: &Env, transaction: &Transaction) -> Result<(), MultisigError> {
+        match transaction.status {
+            TransactionStatus::Pending if Self::is_overdue(env, transaction) => {
+                Err(MultisigError::TransactionExpired)
+            },
+            TransactionStatus::Pending => Ok(()),
+            TransactionStatus::Draft => Err(MultisigError::Unauthorized),
+            TransactionStatus::Executed => Err(MultisigError::TransactionExecuted),
+            TransactionStatus::Cancelled => Err(MultisigError::TransactionCancelled),
+            TransactionStatus::Expired => Err(MultisigError::TransactionExpired),
+        }
+    }
 
-        env.events().publish(
-            (symbol_short!("submit"), new_count),
-            (caller, to, amount, token)
-        );
+    /// Approvals a transaction needs before it can execute, after token
+    /// overrides and reductions for trusted recipients.
+    fn required_approvals_for(env: &Env, config: &MultisigConfig, transaction: &Transaction) -> u32 {
+        if Self::fast_lane_applies(env, config, transaction) {
+            return 1;
+        }
 
-        Ok(new_count)
+        let standard = Self::standard_approvals_for(env, config, transaction);
+        match Self::spending_limit_threshold(env, transaction) {
+            Some(threshold) => threshold.min(standard),
+            None => standard,
+        }
     }
 
+    /// Approvals needed outside the fast lane and spending limits.
+    fn standard_approvals_for(env: &Env, config: &MultisigConfig, transaction: &Transaction) -> u32 {
+        if let TransactionKind::Config(ConfigChange::RecoverQuorum(_)) = transaction.kind {
+            if Self::is_quorum_alarm_raised(env.clone()) {
+                return Self::active_owner_count(env, config).clamp(1, config.required_approvals);
+            }
+        }
+        let required = Self::token_threshold_for(env, config, transaction);
+        if transaction.kind != TransactionKind::Transfer {
+            return required;
+        }
 
-    pub fn approve_transaction(
-        env: Env, 
-        caller: Address,
-        transaction_id: u32
-    ) -> Result<(), MultisigError> {
-        caller.require_auth();
-        Self::verify_owner(&env, &caller)?;
+        let trusted_key = (TRUSTED_KEY, transaction.to.clone(), transaction.token.clone());
+        let required = match env.storage().persistent().get::<_, TrustedRecipient>(&trusted_key) {
+            Some(trusted) if transaction.amount <= trusted.max_amount => trusted.threshold.min(required),
+            _ => required,
+        };
 
-        let tx_key = (TX_KEY, transaction_id);
-        let mut transaction: Transaction = env.storage().persistent().get(&tx_key)
-            .ok_or(MultisigError::TransactionNotFound)?;
+        Self::expedited_approvals(env, transaction, required)
+    }
 
-        if transaction.executed {
-            return Err(MultisigError::TransactionExecuted);
+    /// The token's threshold override for proposals that move or approve it,
+    /// contract calls into it included, capped at what the owners can reach;
+    /// otherwise the wallet threshold.
+    fn token_threshold_for(env: &Env, config: &MultisigConfig, transaction: &Transaction) -> u32 {
+        let moves_token = match &transaction.kind {
+            TransactionKind::Batch(operations) => {
+                let mut required = config.required_approvals;
+                for leg in Self::batch_legs(env, transaction, operations.clone()).iter() {
+                    required = required.max(Self::token_threshold_for(env, config, &leg));
+                }
+                return required;
+            },
+            TransactionKind::ContractCall(call) => matches!(Self::token_movement(env, call), Ok(Some(_))),
+            TransactionKind::Transfer | TransactionKind::Claimable(_) | TransactionKind::Approve(_) => true,
+            _ => false,
+        };
+        if !moves_token {
+            return config.required_approvals;
         }
 
-        let approval_key = (APPROVAL_KEY, transaction_id);
-        let mut approvals: Vec<Address> = env.storage().persistent().get(&approval_key)
-            .unwrap_or_else(|| Vec::new(&env));
+        env.storage().persistent().get::<_, u32>(&(TOKEN_THRESHOLD_KEY, transaction.token.clone()))
+            .map_or(config.required_approvals, |threshold| threshold.min(Self::voting_weight(config)))
+    }
 
-        if approvals.contains(&caller) {
-            return Err(MultisigError::AlreadyApproved);
+    /// Amount the proposal moves: the sum of its legs for a batch, the
+    /// call's amount for a token-moving contract call, its own otherwise.
+    pub(crate) fn moved_amount(env: &Env, transaction: &Transaction) -> i128 {
+        match &transaction.kind {
+            TransactionKind::Batch(operations) => Self::batch_legs(env, transaction, operations.clone())
+                .iter()
+                .map(|leg| Self::moved_amount(env, &leg))
+                .fold(0, i128::saturating_add),
+            TransactionKind::ContractCall(call) => match Self::token_movement(env, call) {
+                Ok(Some((_, amount))) => amount,
+                _ => 0,
+            },
+            _ => transaction.amount,
         }
+    }
 
-        let new_approvals = transaction.approvals.checked_add(1)
-            .ok_or(MultisigError::ArithmeticError)?;
-        
-        transaction.approvals = new_approvals;
-        env.storage().persistent().set(&tx_key, &transaction);
-        
-        approvals.push_back(caller.clone());
-        env.storage().persistent().set(&approval_key, &approvals);
-
-        env.events().publish(
-            (symbol_short!("approve"), transaction_id),
-            (caller, new_approvals)
-        );
+    fn ensure_votes_kept(env: &Env, transaction_id: u32) -> Result<(), MultisigError> {
+        let transaction: Transaction = env.storage().persistent().get(&(TX_KEY, transaction_id))
+            .ok_or(MultisigError::TransactionNotFound)?;
+        if transaction.votes_digest.is_some() {
+            return Err(MultisigError::VotesPruned);
+        }
 
         Ok(())
     }
 
-    pub fn execute_transaction(
-        env: Env, 
-        caller: Address,
-        transaction_id: u32
-    ) -> Result<(), MultisigError> {
-        caller.require_auth();
-        Self::verify_owner(&env, &caller)?;
+    /// Marks a change to the signer set or a threshold, which invalidates
+    /// signatures prepared against the previous configuration. The caller
+    /// stores `config`.
+    pub(crate) fn bump_epoch(env: &Env, config: &mut MultisigConfig) -> Result<(), MultisigError> {
+        config.epoch = config.epoch.checked_add(1).ok_or(MultisigError::ArithmeticError)?;
+        Self::publish_event(env, (Symbol::new(env, "epoch_bump"),), config.epoch);
 
-        let tx_key = (TX_KEY, transaction_id);
-        let mut transaction: Transaction = env.storage().persistent().get(&tx_key)
-            .ok_or(MultisigError::TransactionNotFound)?;
+        Ok(())
+    }
 
-        if transaction.executed {
-            return Err(MultisigError::TransactionExecuted);
+    fn validate_threshold(config: &MultisigConfig, threshold: u32) -> Result<(), MultisigError> {
+        if threshold == 0 || threshold > Self::voting_weight(config) {
+            return Err(MultisigError::InvalidThreshold);
         }
 
-        let config = Self::get_config(&env)?;
+        Ok(())
+    }
+
+    fn validate_transaction_inputs(
+        env: &Env,
+        to: &Address,
+        amount: i128,
+        token: &Address,
+    ) -> Result<(), MultisigError> {
+        Self::check_transfer_inputs(env, to, amount, token, true)
+    }
 
-        if transaction.approvals < config.required_approvals {
-            return Err(MultisigError::InsufficientApprovals);
+    /// Submission-time checks on a transfer. With `cache_decimals` unset
+    /// nothing is written, so views can run them too.
+    fn check_transfer_inputs(
+        env: &Env,
+        to: &Address,
+        amount: i128,
+        token: &Address,
+        cache_decimals: bool,
+    ) -> Result<(), MultisigError> {
+        if amount <= 0 {
+            return Err(MultisigError::InvalidAmount);
+        }
+        if Self::is_unusable_address(env, token) {
+            return Err(MultisigError::InvalidToken);
         }
+        if Self::is_unusable_address(env, to) {
+            return Err(MultisigError::InvalidAddress);
+        }
+        
+        Self::check_amount_sanity(env, token, amount, cache_decimals)?;
+        Self::check_token_policy(env, token, to, amount)
+    }
 
-        transaction.executed = true;
-        env.storage().persistent().set(&tx_key, &transaction);
+    /// The wallet itself or a zero address, neither of which can be a token,
+    /// owner or transfer recipient.
+    fn is_unusable_address(env: &Env, address: &Address) -> bool {
+        *address == env.current_contract_address()
+            || *address == Address::from_str(env, ZERO_ACCOUNT)
+            || *address == Address::from_str(env, ZERO_CONTRACT)
+    }
 
-        let token_client = token::Client::new(&env, &transaction.token);
-        
+    /// Token decimals, fetched once per token and cached when `cache` is set.
+    fn token_decimals(env: &Env, token: &Address, cache: bool) -> u32 {
+        let key = (DECIMALS_KEY, token.clone());
+        if let Some(decimals) = env.storage().persistent().get(&key) {
+            return decimals;
+        }
 
-        match token_client.try_transfer(
-            &env.current_contract_address(),
-            &transaction.to,
-            &transaction.amount
-        ) {
-            Ok(_) => {
-                env.events().publish(
-                    (symbol_short!("execute"), transaction_id),
-                    (caller, transaction.to.clone(), transaction.amount, transaction.token.clone())
-                );
-                Ok(())
-            },
-            Err(_) => {
-                transaction.executed = false;
-                env.storage().persistent().set(&tx_key, &transaction);
-                Err(MultisigError::TokenTransferFailed)
-            }
+        let decimals = token::Client::new(env, token).decimals();
+        if cache {
+            env.storage().persistent().set(&key, &decimals);
         }
+        decimals
     }
 
+    /// Catches amounts entered in whole units instead of base units, or with
+    /// the decimals counted twice, before anyone approves them.
+    fn check_amount_sanity(
+        env: &Env,
+        token: &Address,
+        amount: i128,
+        cache_decimals: bool,
+    ) -> Result<(), MultisigError> {
+        let Some(max_whole_units) = Self::get_config(env)?.max_whole_units else {
+            return Ok(());
+        };
+
+        let exponent = max_whole_units.saturating_add(Self::token_decimals(env, token, cache_decimals));
+        // A bound beyond i128 cannot be exceeded.
+        let Some(limit) = 10i128.checked_pow(exponent) else {
+            return Ok(());
+        };
+        if amount > limit {
+            return Err(MultisigError::AmountImplausible);
+        }
 
-    
-    pub fn get_transaction(
-        env: Env,
-        caller: Address,
-        transaction_id: u32
-    ) -> Result<Transaction, MultisigError> {
-        caller.require_auth();
-        Self::verify_owner(&env, &caller)?;
-        env.storage().persistent().get(&(TX_KEY, transaction_id))
-            .ok_or(MultisigError::TransactionNotFound)
+        Ok(())
+    }
+
+    fn resolve_amount(env: &Env, token: &Address, amount: &PaymentAmount) -> Result<i128, MultisigError> {
+        let indexed = match amount {
+            PaymentAmount::Fixed(amount) => return Ok(*amount),
+            PaymentAmount::Indexed(indexed) => indexed,
+        };
+
+        let oracle = PriceOracleClient::new(env, &indexed.oracle);
+        let price = oracle.lastprice(&OracleAsset::Stellar(token.clone()))
+            .ok_or(MultisigError::PriceUnavailable)?;
+        if price.price <= 0 {
+            return Err(MultisigError::PriceUnavailable);
+        }
+        if env.ledger().timestamp().saturating_sub(price.timestamp) > indexed.max_price_age {
+            return Err(MultisigError::StalePrice);
+        }
+
+        let scale = 10i128.checked_pow(oracle.decimals()).ok_or(MultisigError::ArithmeticError)?;
+        indexed.value.checked_mul(scale)
+            .map(|scaled| scaled / price.price)
+            .ok_or(MultisigError::ArithmeticError)
+    }
+
+    /// Rejects spending that would dip into an active savings lock, unless
+    /// `unanimous` approval overrides it.
+    pub(crate) fn check_savings_lock(
+        env: &Env,
+        token: &Address,
+        amount: i128,
+        unanimous: bool,
+    ) -> Result<(), MultisigError> {
+        let Some(lock) = env.storage().persistent()
+            .get::<_, SavingsLock>(&(SAVINGS_KEY, token.clone())) else {
+            return Ok(());
+        };
+
+        if unanimous || env.ledger().sequence() >= lock.unlock_ledger {
+            return Ok(());
+        }
+
+        let balance = token::Client::new(env, token).balance(&env.current_contract_address());
+        if amount > balance - lock.amount {
+            return Err(MultisigError::SavingsLocked);
+        }
+
+        Ok(())
+    }
+
+    fn check_token_policy(
+        env: &Env,
+        token: &Address,
+        to: &Address,
+        amount: i128,
+    ) -> Result<(), MultisigError> {
+        let Some(policy) = env.storage().persistent()
+            .get::<_, TokenPolicy>(&(TOKEN_POLICY_KEY, token.clone())) else {
+            return Ok(());
+        };
+
+        if let Some(max_amount) = policy.max_amount {
+            if amount > max_amount {
+                return Err(MultisigError::AmountExceedsLimit);
+            }
+        }
+
+        if !policy.allowed_recipients.is_empty() && !policy.allowed_recipients.contains(to) {
+            return Err(MultisigError::RecipientNotAllowed);
+        }
+
+        Ok(())
+    }
+
+ 
+    fn content_hash(
+        env: &Env,
+        to: &Address,
+        amount: i128,
+        token: &Address,
+        data: &BytesN<32>,
+    ) -> BytesN<32> {
+        let content = (to.clone(), token.clone(), amount, data.clone()).to_xdr(env);
+        env.crypto().sha256(&content).into()
+    }
+
+    fn find_pending_duplicate(env: &Env, hash: &BytesN<32>) -> Option<u32> {
+        let existing_id: u32 = env.storage().persistent().get(&(CONTENT_KEY, hash.clone()))?;
+        let existing: Transaction = env.storage().persistent().get(&(TX_KEY, existing_id))?;
+        if Self::ensure_pending(env, &existing).is_ok() {
+            Some(existing_id)
+        } else {
+            None
+        }
+    }
+
+    /// Replaces the vote lists of a closed proposal with a digest when the
+    /// retention policy asks for it.
+    fn retention_expired(env: &Env, config: &MultisigConfig, transaction_id: u32, transaction: &Transaction) -> bool {
+        if matches!(transaction.status, TransactionStatus::Pending | TransactionStatus::Draft) {
+            return false;
+        }
+
+        match config.retention_policy {
+            RetentionPolicy::KeepForever => false,
+            RetentionPolicy::PruneClosed => matches!(
+                transaction.status,
+                TransactionStatus::Cancelled | TransactionStatus::Expired
+            ),
+            RetentionPolicy::KeepLatest(count) => {
+                transaction_id.saturating_add(count) <= Self::transaction_count(env)
+            },
+            RetentionPolicy::KeepForLedgers(ledgers) => transaction.closed_at
+                .is_some_and(|closed_at| env.ledger().sequence() >= closed_at.saturating_add(ledgers)),
+        }
+    }
+
+    /// Prunes the votes of a closed proposal if the retention policy says so.
+    /// The caller stores the updated transaction.
+    fn prune_votes(env: &Env, config: &MultisigConfig, transaction_id: u32, transaction: &mut Transaction) {
+        if transaction.votes_digest.is_some() || !Self::retention_expired(env, config, transaction_id, transaction) {
+            return;
+        }
+
+        Self::compress_votes(env, config, transaction_id, transaction);
+    }
+
+    /// Replaces a closed proposal's approval and rejection entries with a
+    /// digest of them. The caller stores the updated transaction.
+    pub(crate) fn compress_votes(env: &Env, config: &MultisigConfig, transaction_id: u32, transaction: &mut Transaction) {
+        let rejection_key = (REJECTION_KEY, transaction_id);
+        let approvals = Self::approvers(env, config, transaction_id);
+        let rejections: Vec<Address> = env.storage().persistent().get(&rejection_key)
+            .unwrap_or_else(|| Vec::new(env));
+
+        let digest: BytesN<32> = env.crypto().sha256(&(approvals.clone(), rejections).to_xdr(env)).into();
+        for approver in approvals.iter() {
+            env.storage().persistent().remove(&(APPROVAL_KEY, transaction_id, approver));
+        }
+        if let Some(policy) = Self::get_policy_signer(env.clone()) {
+            env.storage().persistent().remove(&(APPROVAL_KEY, transaction_id, policy));
+        }
+        env.storage().persistent().remove(&rejection_key);
+        env.storage().persistent().remove(&(REJECTION_REASON_KEY, transaction_id));
+        Self::clear_approval_caps(env, transaction_id);
+
+        Self::publish_event(env, (symbol_short!("prune"), transaction_id), digest.clone());
+        transaction.votes_digest = Some(digest);
+    }
+
+    /// Approvals live under one key per `(transaction, owner)`, so concurrent
+    /// approvals of a proposal never write the same entry.
+    pub(crate) fn has_approved(env: &Env, transaction_id: u32, owner: &Address) -> bool {
+        env.storage().persistent().has(&(APPROVAL_KEY, transaction_id, owner.clone()))
+    }
+
+    /// Records the approval along with the ledger it was given at and the
+    /// content it was given for.
+    pub(crate) fn store_approval(env: &Env, transaction_id: u32, owner: &Address, transaction: &Transaction) {
+        let record = ApprovalRecord {
+            approved_at: env.ledger().sequence(),
+            content_hash: Self::proposal_hash(env, transaction),
+        };
+        env.storage().persistent().set(&(APPROVAL_KEY, transaction_id, owner.clone()), &record);
+    }
+
+    pub(crate) fn approval_record(env: &Env, transaction_id: u32, owner: &Address) -> Option<ApprovalRecord> {
+        env.storage().persistent().get(&(APPROVAL_KEY, transaction_id, owner.clone()))
+    }
+
+    /// Hash of everything an approver signs off on.
+    fn proposal_hash(env: &Env, transaction: &Transaction) -> BytesN<32> {
+        let content = (
+            transaction.to.clone(),
+            transaction.amount,
+            transaction.token.clone(),
+            transaction.data.clone(),
+            transaction.kind.clone(),
+            transaction.submitter.clone(),
+        ).to_xdr(env);
+        env.crypto().sha256(&content).into()
+    }
+
+    /// Fails if any recorded approval was given for different content than
+    /// the proposal now holds.
+    pub(crate) fn ensure_approvals_current(
+        env: &Env,
+        config: &MultisigConfig,
+        transaction_id: u32,
+        transaction: &Transaction,
+    ) -> Result<(), MultisigError> {
+        let current = Self::proposal_hash(env, transaction);
+        for owner in config.owners.iter() {
+            if let Some(record) = Self::approval_record(env, transaction_id, &owner) {
+                if record.content_hash != current {
+                    return Err(MultisigError::InsufficientApprovals);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Current owners who approved `transaction_id`, in owner order.
+    pub(crate) fn approvers(env: &Env, config: &MultisigConfig, transaction_id: u32) -> Vec<Address> {
+        let mut approvers = Vec::new(env);
+        for owner in config.owners.iter() {
+            if Self::has_approved(env, transaction_id, &owner) {
+                approvers.push_back(owner);
+            }
+        }
+        approvers
+    }
+
+    /// Adds `caller`'s approval to a pending proposal. The caller has already
+    /// been authenticated and checked to be an owner.
+    fn record_approval(env: &Env, caller: &Address, transaction_id: u32) -> Result<(), MultisigError> {
+        let tx_key = (TX_KEY, transaction_id);
+        let mut transaction: Transaction = env.storage().persistent().get(&tx_key)
+            .ok_or(MultisigError::TransactionNotFound)?;
+
+        Self::ensure_pending(env, &transaction)?;
+        Self::ensure_not_paused(env, &transaction.kind).map_err(|error| Self::multisig_error(env, error))?;
+
+        if Self::has_approved(env, transaction_id, caller) {
+            return Err(MultisigError::AlreadyApproved);
+        }
+
+        let config = Self::get_config(env)?;
+        Self::ensure_role(&config, caller, OwnerRole::can_vote)?;
+
+        let previous = Self::effective_approvals(env, transaction_id, &transaction);
+        transaction.approvals = transaction.approvals.checked_add(Self::owner_weight(&config, caller))
+            .ok_or(MultisigError::ArithmeticError)?;
+        env.storage().persistent().set(&tx_key, &transaction);
+        Self::store_approval(env, transaction_id, caller, &transaction);
+        Self::touch_transaction(env, &config, transaction_id);
+
+        let new_approvals = Self::effective_approvals(env, transaction_id, &transaction);
+        let required = Self::required_approvals_for(env, &config, &transaction);
+
+        Self::publish_event(
+            env,
+            (symbol_short!("approve"), transaction_id),
+            (caller.clone(), new_approvals, required)
+        );
+
+        if previous < required && new_approvals >= required {
+            Self::publish_quorum_reached(env, &config, transaction_id, &transaction, new_approvals);
+        }
+
+        Ok(())
+    }
+
+    /// Takes `owner`'s approval, worth `weight`, off a pending proposal. A
+    /// quorum certificate no longer backed by enough approvals is withdrawn.
+    pub(crate) fn withdraw_approval(
+        env: &Env,
+        config: &MultisigConfig,
+        transaction_id: u32,
+        transaction: &mut Transaction,
+        owner: &Address,
+        weight: u32,
+    ) {
+        transaction.approvals = transaction.approvals.saturating_sub(weight);
+        env.storage().persistent().remove(&(APPROVAL_KEY, transaction_id, owner.clone()));
+        Self::clear_approval_cap(env, transaction_id, owner);
+
+        let approvals = Self::effective_approvals(env, transaction_id, transaction);
+        if approvals < Self::required_approvals_for(env, config, transaction) {
+            transaction.approved_at = None;
+            Self::withdraw_quorum_certificate(env, transaction_id);
+        }
+        env.storage().persistent().set(&(TX_KEY, transaction_id), transaction);
+    }
+
+    fn record_rejection(
+        env: &Env,
+        caller: &Address,
+        transaction_id: u32,
+        reason: RejectionReason,
+    ) -> Result<(), MultisigError> {
+        let tx_key = (TX_KEY, transaction_id);
+        let mut transaction: Transaction = env.storage().persistent().get(&tx_key)
+            .ok_or(MultisigError::TransactionNotFound)?;
+
+        Self::ensure_pending(env, &transaction)?;
+
+        let rejection_key = (REJECTION_KEY, transaction_id);
+        let mut rejections: Vec<Address> = env.storage().persistent().get(&rejection_key)
+            .unwrap_or_else(|| Vec::new(env));
+
+        if rejections.contains(caller) {
+            return Err(MultisigError::AlreadyRejected);
+        }
+
+        let config = Self::get_config(env)?;
+        Self::ensure_role(&config, caller, OwnerRole::can_vote)?;
+
+        let new_rejections = transaction.rejections.checked_add(Self::owner_weight(&config, caller))
+            .ok_or(MultisigError::ArithmeticError)?;
+
+        transaction.rejections = new_rejections;
+        env.storage().persistent().set(&tx_key, &transaction);
+
+        rejections.push_back(caller.clone());
+        env.storage().persistent().set(&rejection_key, &rejections);
+
+        let reason_key = (REJECTION_REASON_KEY, transaction_id);
+        let mut reasons: Vec<RejectionReason> = env.storage().persistent().get(&reason_key)
+            .unwrap_or_else(|| Vec::new(env));
+        reasons.push_back(reason);
+        env.storage().persistent().set(&reason_key, &reasons);
+        Self::touch_transaction(env, &config, transaction_id);
+
+        Self::publish_event(
+            env,
+            (symbol_short!("reject"), transaction_id),
+            (caller.clone(), new_rejections, reason)
+        );
+
+        Ok(())
+    }
+
+    /// Closes a pending proposal with `status`: prunes its votes, drops it
+    /// from the pending index, withdraws any quorum certificate and settles
+    /// its bond. The caller publishes its own event.
+    pub(crate) fn close_pending(
+        env: &Env,
+        config: &MultisigConfig,
+        transaction_id: u32,
+        transaction: &mut Transaction,
+        status: TransactionStatus,
+        refund_bond: bool,
+    ) -> Result<(), MultisigError> {
+        transaction.status = status;
+        transaction.closed_at = Some(env.ledger().sequence());
+        Self::prune_votes(env, config, transaction_id, transaction);
+        env.storage().persistent().set(&(TX_KEY, transaction_id), transaction);
+        Self::track_pending(env, transaction_id, false);
+        Self::withdraw_quorum_certificate(env, transaction_id);
+        Self::settle_bond(env, transaction_id, transaction, refund_bond)?;
+        Self::notify_listener(env, transaction_id, transaction);
+
+        Ok(())
+    }
+
+    fn cancel_pending(env: &Env, caller: &Address, transaction_id: u32) -> Result<(), MultisigError> {
+        let tx_key = (TX_KEY, transaction_id);
+        let mut transaction: Transaction = env.storage().persistent().get(&tx_key)
+            .ok_or(MultisigError::TransactionNotFound)?;
+
+        Self::ensure_pending(env, &transaction)?;
+
+        let config = Self::get_config(env)?;
+        if *caller != transaction.submitter && transaction.rejections < config.cancel_threshold {
+            return Err(MultisigError::InsufficientRejections);
+        }
+
+        // Once a rejection quorum stands the bond is forfeit, whoever cancels.
+        let refund_bond = transaction.rejections < config.cancel_threshold;
+        Self::close_pending(env, &config, transaction_id, &mut transaction, TransactionStatus::Cancelled, refund_bond)?;
+
+        Self::publish_event(
+            env,
+            (symbol_short!("cancel"), transaction_id),
+            (caller.clone(), transaction.rejections)
+        );
+
+        Ok(())
+    }
+
+    /// Emitted once per transaction, on the approval that first meets the
+    /// threshold. Also starts any timelock; the caller has already stored
+    /// `transaction`.
+    fn publish_quorum_reached(
+        env: &Env,
+        config: &MultisigConfig,
+        transaction_id: u32,
+        transaction: &Transaction,
+        approvals: u32,
+    ) {
+        let mut approved = transaction.clone();
+        approved.approved_at = Some(env.ledger().sequence());
+        env.storage().persistent().set(&(TX_KEY, transaction_id), &approved);
+        Self::issue_quorum_certificate(env, config, transaction_id, transaction, approvals);
+        Self::publish_event(env, (symbol_short!("quorum"), transaction_id), approvals);
+    }
+
+    fn store_transaction(
+        env: &Env,
+        caller: &Address,
+        to: Address,
+        amount: i128,
+        token: Address,
+        data: BytesN<32>,
+        kind: TransactionKind,
+    ) -> Result<u32, MultisigError> {
+        let config = Self::get_config(env)?;
+        Self::ensure_role(&config, caller, OwnerRole::can_propose)?;
+
+        let id = Self::next_transaction_id(env)?;
+        let transaction = Self::new_transaction(caller, to, amount, token, data, kind, TransactionStatus::Pending);
+        Self::open_transaction(env, &config, id, transaction)?;
+
+        Ok(id)
+    }
+
+    /// Reserves the next proposal id.
+    fn next_transaction_id(env: &Env) -> Result<u32, MultisigError> {
+        let id = Self::transaction_count(env).checked_add(1)
+            .ok_or(MultisigError::ArithmeticError)?;
+        env.storage().instance().set(&TX_COUNT_KEY, &id);
+        Self::extend_core_ttl(env);
+
+        Ok(id)
+    }
+
+    fn new_transaction(
+        caller: &Address,
+        to: Address,
+        amount: i128,
+        token: Address,
+        data: BytesN<32>,
+        kind: TransactionKind,
+        status: TransactionStatus,
+    ) -> Transaction {
+        Transaction {
+            to,
+            amount,
+            token,
+            data,
+            kind,
+            status,
+            approvals: 0,
+            rejections: 0,
+            submitter: caller.clone(),
+            expires_at: None,
+            votes_digest: None,
+            closed_at: None,
+            priority: Priority::Normal,
+            approved_at: None,
+        }
+    }
+
+    /// Opens `transaction` for approval under `id`: applies the duplicate
+    /// policy, starts its lifetime, records the submitter's approval and locks
+    /// the bond. Its content is fixed from here on.
+    fn open_transaction(
+        env: &Env,
+        config: &MultisigConfig,
+        id: u32,
+        mut transaction: Transaction,
+    ) -> Result<(), MultisigError> {
+        Self::ensure_not_paused(env, &transaction.kind).map_err(|error| Self::multisig_error(env, error))?;
+        let caller = transaction.submitter.clone();
+        let auto_approve = config.submitter_auto_approve && Self::owner_role(config, &caller).can_vote();
+
+        if transaction.kind == TransactionKind::Transfer {
+            let hash = Self::content_hash(env, &transaction.to, transaction.amount, &transaction.token, &transaction.data);
+            if let Some(existing_id) = Self::find_pending_duplicate(env, &hash) {
+                match config.duplicate_policy {
+                    DuplicatePolicy::Reject => return Err(MultisigError::DuplicateTransaction),
+                    DuplicatePolicy::Warn => {
+                        Self::publish_event(env, (symbol_short!("duplicate"), id), existing_id);
+                    },
+                    DuplicatePolicy::Allow => {},
+                }
+            }
+            env.storage().persistent().set(&(CONTENT_KEY, hash), &id);
+        }
+        if Self::pays_recipient(&transaction.kind) {
+            Self::screen_recipient(env, id, &transaction, false)?;
+        }
+
+        transaction.status = TransactionStatus::Pending;
+        transaction.approvals = if auto_approve { Self::owner_weight(config, &caller) } else { 0 };
+        let lifetime_end = match config.proposal_lifetime {
+            0 => None,
+            lifetime => Some(
+                env.ledger().sequence().checked_add(lifetime)
+                    .ok_or(MultisigError::ArithmeticError)?
+            ),
+        };
+        // A deadline the submitter asked for can only shorten the lifetime.
+        transaction.expires_at = match (transaction.expires_at, lifetime_end) {
+            (Some(requested), Some(end)) => Some(requested.min(end)),
+            (requested, end) => requested.or(end),
+        };
+
+        Self::track_pending(env, id, true);
+        env.storage().persistent().set(&(TX_KEY, id), &transaction);
+        Self::notify_listener(env, id, &transaction);
+
+        if auto_approve {
+            Self::store_approval(env, id, &caller, &transaction);
+        }
+        Self::touch_transaction(env, config, id);
+        Self::lock_bond(env, config, &caller, id)?;
+
+        Self::publish_event(
+            env,
+            (symbol_short!("submit"), id),
+            (caller, transaction.to.clone(), transaction.amount, transaction.token.clone())
+        );
+
+        let required = Self::required_approvals_for(env, config, &transaction);
+        if transaction.approvals > 0 && transaction.approvals >= required {
+            Self::publish_quorum_reached(env, config, id, &transaction, transaction.approvals);
+        }
+
+        Ok(())
+    }
+
+    pub fn submit_transaction(
+        env: Env,
+        caller: Address,
+        to: Address,
+        amount: i128,
+        token: Address,
+        data: BytesN<32>,
+    ) -> Result<u32, MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+        
+        Self::validate_transaction_inputs(&env, &to, amount, &token)?;
+        
+        Self::store_transaction(&env, &caller, to, amount, token, data, TransactionKind::Transfer)
+    }
+
+    /// Like `submit_transaction`, but the proposal can no longer be approved
+    /// or executed after ledger `expires_at`, or sooner if the wallet's
+    /// proposal lifetime ends first.
+    pub fn submit_expiring_transaction(
+        env: Env,
+        caller: Address,
+        to: Address,
+        amount: i128,
+        token: Address,
+        data: BytesN<32>,
+        expires_at: u32,
+    ) -> Result<u32, MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+        if expires_at <= env.ledger().sequence() {
+            return Err(MultisigError::InvalidSchedule);
+        }
+        Self::validate_transaction_inputs(&env, &to, amount, &token)?;
+
+        let config = Self::get_config(&env)?;
+        Self::ensure_role(&config, &caller, OwnerRole::can_propose)?;
+        let id = Self::next_transaction_id(&env)?;
+        let mut transaction = Self::new_transaction(
+            &caller,
+            to,
+            amount,
+            token,
+            data,
+            TransactionKind::Transfer,
+            TransactionStatus::Pending,
+        );
+        transaction.expires_at = Some(expires_at);
+        Self::open_transaction(&env, &config, id, transaction)?;
+
+        Ok(id)
+    }
+
+    /// Runs the checks `submit_transaction` would apply to this transfer
+    /// without submitting it or writing anything, so a front-end can report
+    /// problems before anyone signs.
+    pub fn validate_submission(
+        env: Env,
+        to: Address,
+        amount: i128,
+        token: Address,
+        data: BytesN<32>,
+    ) -> Result<(), MultisigError> {
+        let config = Self::get_config(&env)?;
+
+        Self::check_transfer_inputs(&env, &to, amount, &token, false)?;
+
+        if config.duplicate_policy == DuplicatePolicy::Reject {
+            let hash = Self::content_hash(&env, &to, amount, &token, &data);
+            if Self::find_pending_duplicate(&env, &hash).is_some() {
+                return Err(MultisigError::DuplicateTransaction);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn submit_config_change(
+        env: Env,
+        caller: Address,
+        change: ConfigChange,
+    ) -> Result<u32, MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        let this = env.current_contract_address();
+        let data = BytesN::from_array(&env, &[0; 32]);
+        Self::store_transaction(&env, &caller, this.clone(), 0, this, data, TransactionKind::Config(change))
+    }
+
+    /// Submits a transfer built from a saved template, with `overrides` taking
+    /// precedence over the template's values.
+    pub fn submit_from_template(
+        env: Env,
+        caller: Address,
+        name: Symbol,
+        overrides: TransferTemplate,
+    ) -> Result<u32, MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        let template: TransferTemplate = env.storage().persistent().get(&(TEMPLATE_KEY, name))
+            .ok_or(MultisigError::TemplateNotFound)?;
+
+        let to = overrides.to.or(template.to).ok_or(MultisigError::IncompleteTemplate)?;
+        let amount = overrides.amount.or(template.amount).ok_or(MultisigError::IncompleteTemplate)?;
+        let token = overrides.token.or(template.token).ok_or(MultisigError::IncompleteTemplate)?;
+        let data = overrides.data.or(template.data)
+            .unwrap_or_else(|| BytesN::from_array(&env, &[0; 32]));
+
+        Self::validate_transaction_inputs(&env, &to, amount, &token)?;
+
+        Self::store_transaction(&env, &caller, to, amount, token, data, TransactionKind::Transfer)
+    }
+
+
+    pub fn approve_transaction(
+        env: Env, 
+        caller: Address,
+        transaction_id: u32
+    ) -> Result<(), MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        Self::record_approval(&env, &caller, transaction_id)
+    }
+
+    /// Withdraws `caller`'s approval from a pending proposal.
+    pub fn revoke_approval(
+        env: Env,
+        caller: Address,
+        transaction_id: u32
+    ) -> Result<(), MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        let mut transaction: Transaction = env.storage().persistent().get(&(TX_KEY, transaction_id))
+            .ok_or(MultisigError::TransactionNotFound)?;
+        Self::ensure_pending(&env, &transaction)?;
+        if !Self::has_approved(&env, transaction_id, &caller) {
+            return Err(MultisigError::Unauthorized);
+        }
+
+        let config = Self::get_config(&env)?;
+        let weight = Self::owner_weight(&config, &caller);
+        Self::withdraw_approval(&env, &config, transaction_id, &mut transaction, &caller, weight);
+
+        Self::publish_event(&env, (symbol_short!("revoke"), transaction_id), (caller, transaction.approvals));
+
+        Ok(())
+    }
+
+    pub fn reject_transaction(
+        env: Env,
+        caller: Address,
+        transaction_id: u32,
+        reason: RejectionReason,
+    ) -> Result<(), MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        Self::record_rejection(&env, &caller, transaction_id, reason)
+    }
+
+    /// The submitter may withdraw their own proposal at any time; anyone else
+    /// needs `cancel_threshold` reject votes recorded first. A proposal
+    /// cancelled with that many rejections forfeits its bond.
+    pub fn cancel_transaction(
+        env: Env,
+        caller: Address,
+        transaction_id: u32
+    ) -> Result<(), MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        Self::cancel_pending(&env, &caller, transaction_id)
+    }
+
+    /// Permissionless sweep: prunes the votes of every proposal in
+    /// `transaction_ids` that the retention policy no longer keeps. Returns how
+    /// many were pruned.
+    pub fn prune_transactions(env: Env, transaction_ids: Vec<u32>) -> Result<u32, MultisigError> {
+        let config = Self::get_config(&env)?;
+        let mut pruned = 0;
+        for transaction_id in transaction_ids.iter() {
+            let tx_key = (TX_KEY, transaction_id);
+            let Some(mut transaction) = env.storage().persistent().get::<_, Transaction>(&tx_key) else {
+                continue;
+            };
+            if transaction.votes_digest.is_some() {
+                continue;
+            }
+
+            Self::prune_votes(&env, &config, transaction_id, &mut transaction);
+            if transaction.votes_digest.is_some() {
+                env.storage().persistent().set(&tx_key, &transaction);
+                pruned += 1;
+            }
+        }
+
+        Ok(pruned)
+    }
+
+    /// Permissionless sweep: moves every overdue pending proposal in `transaction_ids`
+    /// to `Expired`. Ids that are unknown, settled or still open are skipped.
+    /// Returns how many proposals were expired.
+    pub fn expire_transactions(env: Env, transaction_ids: Vec<u32>) -> Result<u32, MultisigError> {
+        let config = Self::get_config(&env)?;
+        let mut expired = 0;
+        for transaction_id in transaction_ids.iter() {
+            let tx_key = (TX_KEY, transaction_id);
+            let Some(mut transaction) = env.storage().persistent().get::<_, Transaction>(&tx_key) else {
+                continue;
+            };
+
+            if transaction.status != TransactionStatus::Pending || !Self::is_overdue(&env, &transaction) {
+                continue;
+            }
+
+            Self::close_pending(&env, &config, transaction_id, &mut transaction, TransactionStatus::Expired, true)?;
+            Self::publish_event(&env, (symbol_short!("expire"), transaction_id), transaction.expires_at);
+            expired += 1;
+        }
+
+        Ok(expired)
+    }
+
+    fn apply_config_change(
+        env: &Env,
+        transaction_id: u32,
+        change: ConfigChange,
+        transaction: &Transaction,
+    ) -> Result<(), MultisigError> {
+        match change {
+            ConfigChange::AddOwner(owner) => Self::add_owner(env, owner)?,
+            ConfigChange::RemoveOwner(owner) => Self::remove_owner(env, owner)?,
+            ConfigChange::SetThreshold(new_threshold) => {
+                let mut config = Self::get_config(env)?;
+                Self::validate_threshold(&config, new_threshold)?;
+                config.required_approvals = new_threshold;
+                Self::bump_epoch(env, &mut config)?;
+                env.storage().persistent().set(&CONFIG_KEY, &config);
+                Self::publish_event(env, (symbol_short!("threshold"),), new_threshold);
+                Self::refresh_quorum_alarm(env)?;
+            },
+            ConfigChange::SetCancelThreshold(new_threshold) => {
+                let mut config = Self::get_config(env)?;
+                Self::validate_threshold(&config, new_threshold)?;
+                config.cancel_threshold = new_threshold;
+                Self::bump_epoch(env, &mut config)?;
+                env.storage().persistent().set(&CONFIG_KEY, &config);
+                Self::publish_event(env, (symbol_short!("cancel_th"),), new_threshold);
+            },
+            ConfigChange::SaveTemplate(name, template) => {
+                env.storage().persistent().set(&(TEMPLATE_KEY, name.clone()), &template);
+                Self::publish_event(env, (symbol_short!("tmpl_save"), name), ());
+            },
+            ConfigChange::RemoveTemplate(name) => {
+                let key = (TEMPLATE_KEY, name.clone());
+                if !env.storage().persistent().has(&key) {
+                    return Err(MultisigError::TemplateNotFound);
+                }
+                env.storage().persistent().remove(&key);
+                Self::publish_event(env, (symbol_short!("tmpl_del"), name), ());
+            },
+            ConfigChange::SetDuplicatePolicy(policy) => {
+                let mut config = Self::get_config(env)?;
+                config.duplicate_policy = policy;
+                env.storage().persistent().set(&CONFIG_KEY, &config);
+                Self::publish_event(env, (symbol_short!("dup_pol"),), policy);
+            },
+            ConfigChange::SetProposalLifetime(lifetime) => {
+                let mut config = Self::get_config(env)?;
+                config.proposal_lifetime = lifetime;
+                env.storage().persistent().set(&CONFIG_KEY, &config);
+                Self::publish_event(env, (symbol_short!("lifetime"),), lifetime);
+            },
+            ConfigChange::SetRetentionPolicy(policy) => {
+                let mut config = Self::get_config(env)?;
+                config.retention_policy = policy;
+                env.storage().persistent().set(&CONFIG_KEY, &config);
+                Self::publish_event(env, (symbol_short!("retention"),), policy);
+            },
+            ConfigChange::SetAccountMode(mode) => {
+                let mut config = Self::get_config(env)?;
+                if let AccountMode::Enabled(policy) = &mode {
+                    Self::validate_threshold(&config, policy.token_transfer)?;
+                    Self::validate_threshold(&config, policy.contract_call)?;
+                    Self::validate_threshold(&config, policy.create_contract)?;
+                }
+                config.account_mode = mode.clone();
+                env.storage().persistent().set(&CONFIG_KEY, &config);
+                Self::publish_event(env, (symbol_short!("acct_mode"),), mode);
+            },
+            ConfigChange::SetTokenPolicy(token, policy) => {
+                env.storage().persistent().set(&(TOKEN_POLICY_KEY, token.clone()), &policy);
+                Self::publish_event(env, (symbol_short!("tok_pol"), token), policy);
+            },
+            ConfigChange::RemoveTokenPolicy(token) => {
+                env.storage().persistent().remove(&(TOKEN_POLICY_KEY, token.clone()));
+                Self::publish_event(env, (symbol_short!("tok_pol"), token), ());
+            },
+            ConfigChange::SetTokenThreshold(token, threshold) => {
+                let key = (TOKEN_THRESHOLD_KEY, token.clone());
+                match threshold {
+                    Some(threshold) => {
+                        Self::validate_threshold(&Self::get_config(env)?, threshold)?;
+                        env.storage().persistent().set(&key, &threshold);
+                    },
+                    None => env.storage().persistent().remove(&key),
+                }
+                Self::publish_event(env, (symbol_short!("tok_thr"), token), threshold);
+            },
+            ConfigChange::SetDualControl(enabled) => {
+                let mut config = Self::get_config(env)?;
+                config.dual_control = enabled;
+                env.storage().persistent().set(&CONFIG_KEY, &config);
+                Self::publish_event(env, (symbol_short!("dual_ctl"),), enabled);
+            },
+            ConfigChange::SetStrictOrdering(enabled) => Self::set_strict_ordering(env, enabled),
+            ConfigChange::SetUrgentHandling(handling) => {
+                Self::set_urgent_handling(env, &Self::get_config(env)?, handling)?
+            },
+            ConfigChange::SetSubmitterAutoApprove(enabled) => {
+                let mut config = Self::get_config(env)?;
+                config.submitter_auto_approve = enabled;
+                env.storage().persistent().set(&CONFIG_KEY, &config);
+                Self::publish_event(env, (symbol_short!("auto_appr"),), enabled);
+            },
+            ConfigChange::SetTrustedRecipient(recipient, token, trusted) => {
+                let config = Self::get_config(env)?;
+                Self::validate_threshold(&config, trusted.threshold)?;
+                if trusted.max_amount <= 0 {
+                    return Err(MultisigError::InvalidAmount);
+                }
+                env.storage().persistent().set(&(TRUSTED_KEY, recipient.clone(), token.clone()), &trusted);
+                Self::publish_event(env, (symbol_short!("trusted"), recipient, token), trusted);
+            },
+            ConfigChange::RemoveTrustedRecipient(recipient, token) => {
+                env.storage().persistent().remove(&(TRUSTED_KEY, recipient.clone(), token.clone()));
+                Self::publish_event(env, (symbol_short!("trusted"), recipient, token), ());
+            },
+            ConfigChange::SetRequester(requester, allowed) => Self::set_requester(env, requester, allowed)?,
+            ConfigChange::SetSavingsLock(token, lock) => {
+                if lock.amount < 0 {
+                    return Err(MultisigError::InvalidAmount);
+                }
+                let config = Self::get_config(env)?;
+                let savings_key = (SAVINGS_KEY, token.clone());
+                if let Some(current) = env.storage().persistent().get::<_, SavingsLock>(&savings_key) {
+                    let active = env.ledger().sequence() < current.unlock_ledger;
+                    let weakened = lock.amount < current.amount || lock.unlock_ledger < current.unlock_ledger;
+                    if active && weakened && Self::owner_approvals(env, transaction_id, transaction) < Self::voting_weight(&config) {
+                        return Err(MultisigError::SavingsLocked);
+                    }
+                }
+                env.storage().persistent().set(&savings_key, &lock);
+                Self::publish_event(env, (symbol_short!("savings"), token), lock);
+            },
+            ConfigChange::CreateRecurring(payment) => {
+                if payment.interval == 0 {
+                    return Err(MultisigError::InvalidSchedule);
+                }
+                if let PaymentAmount::Fixed(amount) = payment.amount {
+                    Self::validate_transaction_inputs(env, &payment.to, amount, &payment.token)?;
+                }
+                let count: u32 = env.storage().persistent().get(&RECURRING_COUNT_KEY).unwrap_or(0);
+                let payment_id = count.checked_add(1).ok_or(MultisigError::ArithmeticError)?;
+                env.storage().persistent().set(&RECURRING_COUNT_KEY, &payment_id);
+                env.storage().persistent().set(&(RECURRING_KEY, payment_id), &payment);
+                Self::publish_event(env, (symbol_short!("recur_new"), payment_id), payment);
+            },
+            ConfigChange::CancelRecurring(payment_id) => {
+                let key = (RECURRING_KEY, payment_id);
+                if !env.storage().persistent().has(&key) {
+                    return Err(MultisigError::RecurringNotFound);
+                }
+                env.storage().persistent().remove(&key);
+                Self::publish_event(env, (symbol_short!("recur_end"), payment_id), ());
+            },
+            ConfigChange::CreateDcaPlan(plan) => Self::create_dca_plan(env, plan)?,
+            ConfigChange::CancelDcaPlan(plan_id) => Self::cancel_dca_plan(env, plan_id)?,
+            ConfigChange::SetBridge(bridge) => {
+                let mut config = Self::get_config(env)?;
+                config.bridge = bridge.clone();
+                env.storage().persistent().set(&CONFIG_KEY, &config);
+                Self::publish_event(env, (symbol_short!("brdg_set"),), bridge);
+            },
+            ConfigChange::SetListener(listener) => Self::set_listener(env, listener),
+            ConfigChange::SetPolicySigner(policy) => Self::set_policy_signer(env, policy)?,
+            ConfigChange::SetScreener(screener) => Self::set_screener(env, screener)?,
+            ConfigChange::SetMaxWholeUnits(max_whole_units) => {
+                let mut config = Self::get_config(env)?;
+                config.max_whole_units = max_whole_units;
+                env.storage().persistent().set(&CONFIG_KEY, &config);
+                Self::publish_event(env, (symbol_short!("max_units"),), max_whole_units);
+            },
+            ConfigChange::SetProposalBond(requirement) => {
+                Self::validate_bond_requirement(env, &requirement)?;
+                let mut config = Self::get_config(env)?;
+                config.bond = requirement.clone();
+                env.storage().persistent().set(&CONFIG_KEY, &config);
+                Self::publish_event(env, (symbol_short!("bond_set"),), requirement);
+            },
+            ConfigChange::SetExecutorReimbursement(reimbursement) => {
+                Self::validate_reimbursement(env, &reimbursement)?;
+                let mut config = Self::get_config(env)?;
+                config.reimbursement = reimbursement.clone();
+                env.storage().persistent().set(&CONFIG_KEY, &config);
+                Self::publish_event(env, (symbol_short!("reimb_set"),), reimbursement);
+            },
+            ConfigChange::SetPeriodCalendar(calendar) => Self::set_period_calendar(env, calendar)?,
+            ConfigChange::SetTimelock(timelock) => Self::set_timelock(env, timelock)?,
+            ConfigChange::SetFastLane(fast_lane) => {
+                Self::validate_fast_lane(&fast_lane)?;
+                let mut config = Self::get_config(env)?;
+                config.fast_lane = fast_lane.clone();
+                env.storage().persistent().set(&CONFIG_KEY, &config);
+                Self::publish_event(env, (symbol_short!("fast_set"),), fast_lane);
+            },
+            ConfigChange::SetLockdownCooldown(cooldown) => {
+                let mut config = Self::get_config(env)?;
+                config.lockdown_cooldown = cooldown;
+                env.storage().persistent().set(&CONFIG_KEY, &config);
+                Self::publish_event(env, (symbol_short!("cooldown"),), cooldown);
+            },
+            ConfigChange::LiftLockdown => {
+                Self::begin_unlock(env, Self::get_config(env)?.lockdown_cooldown)?;
+            },
+            ConfigChange::AcknowledgeRecovery(owner) => Self::complete_recovery(env, owner)?,
+            ConfigChange::SetEventLabel(label) => Self::set_event_label(env, label),
+            ConfigChange::RevokeAllAllowances => Self::revoke_all_allowances(env)?,
+            ConfigChange::SetPullAllowance(child, allowance) => Self::set_pull_allowance(env, child, allowance)?,
+            ConfigChange::PreAuthorize(payload, expires_at) => {
+                Self::store_preauthorization(env, payload, expires_at)?
+            },
+            ConfigChange::RevokePreAuthorization(payload) => Self::revoke_preauthorization(env, payload),
+            ConfigChange::SetInactivityPeriod(period) => {
+                let mut config = Self::get_config(env)?;
+                config.inactivity_period = period;
+                env.storage().persistent().set(&CONFIG_KEY, &config);
+                Self::publish_event(env, (symbol_short!("inactive"),), period);
+                Self::refresh_quorum_alarm(env)?;
+            },
+            ConfigChange::RecoverQuorum(new_threshold) => Self::recover_quorum(env, new_threshold)?,
+            ConfigChange::UnpauseToken(token) => Self::unpause_token(env, token),
+            ConfigChange::RegisterToken(token) => {
+                if Self::is_unusable_address(env, &token) {
+                    return Err(MultisigError::InvalidToken);
+                }
+                Self::register_token(env, &token, transaction_id)
+            },
+            ConfigChange::DeregisterToken(token) => Self::deregister_token(env, &token),
+            ConfigChange::SetChecklist(kind, items) => Self::set_checklist(env, kind, items)?,
+            ConfigChange::Extended(change) => Self::apply_extended_change(env, change)?,
+        }
+
+        Ok(())
+    }
+
+    fn apply_extended_change(env: &Env, change: ExtendedChange) -> Result<(), MultisigError> {
+        match change {
+            ExtendedChange::SetSpendingLimit(token, limit) => Self::set_spending_limit(env, token, limit)?,
+            ExtendedChange::FundFeePool(token, amount) => Self::fund_fee_pool(env, token, amount)?,
+            ExtendedChange::DrainFeePool(token) => Self::drain_fee_pool(env, token),
+            ExtendedChange::SetAuditor(auditor, access) => Self::set_auditor(env, auditor, access)?,
+            ExtendedChange::SetOwnerWeight(owner, weight) => Self::set_owner_weight(env, owner, weight)?,
+            ExtendedChange::SetOwnerRole(owner, role) => Self::set_owner_role(env, owner, role)?,
+            ExtendedChange::RehearseRecovery(owner) => Self::complete_rehearsal(env, owner)?,
+            ExtendedChange::SetPausers(pausers) => Self::set_pausers(env, pausers)?,
+            ExtendedChange::Unpause => Self::unpause(env),
+            ExtendedChange::ReplaceOwner(replacement) => Self::replace_owner(env, replacement)?,
+            ExtendedChange::Upgrade(new_wasm_hash) => Self::upgrade(env, new_wasm_hash),
+            ExtendedChange::SetStorageTtl(settings) => Self::set_storage_ttl(env, settings)?,
+        }
+
+        Ok(())
+    }
+
+
+    
+    pub fn get_transaction(
+        env: Env,
+        caller: Address,
+        transaction_id: u32
+    ) -> Result<Transaction, MultisigError> {
+        caller.require_auth();
+        Self::verify_viewer(&env, &caller)?;
+        env.storage().persistent().get(&(TX_KEY, transaction_id))
+            .ok_or(MultisigError::TransactionNotFound)
+    }
+
+    /// Proposes a new approval threshold, which takes effect once the
+    /// proposal is approved and executed like any other.
+    pub fn update_threshold(
+        env: Env,
+        caller: Address,
+        new_threshold: u32
+    ) -> Result<u32, MultisigError> {
+        Self::validate_threshold(&Self::get_config(&env)?, new_threshold)?;
+        Self::submit_config_change(env, caller, ConfigChange::SetThreshold(new_threshold))
+    }
+
+    /// Proposes a new cancel threshold; see `update_threshold`.
+    pub fn update_cancel_threshold(
+        env: Env,
+        caller: Address,
+        new_threshold: u32
+    ) -> Result<u32, MultisigError> {
+        Self::validate_threshold(&Self::get_config(&env)?, new_threshold)?;
+        Self::submit_config_change(env, caller, ConfigChange::SetCancelThreshold(new_threshold))
+    }
+
+    pub fn get_owner_count(env: Env) -> Result<u32, MultisigError> {
+        Ok(Self::get_config(&env)?.owners.len())
+    }
+
+    pub fn get_threshold(env: Env) -> Result<u32, MultisigError> {
+        Ok(Self::get_config(&env)?.required_approvals)
+    }
+
+    pub fn get_cancel_threshold(env: Env) -> Result<u32, MultisigError> {
+        Ok(Self::get_config(&env)?.cancel_threshold)
+    }
+
+    pub fn get_transaction_count(env: Env) -> Result<u32, MultisigError> {
+        Self::get_config(&env)?;
+        Ok(Self::transaction_count(&env))
+    }
+
+    /// Proposals still marked pending, including overdue ones not yet swept
+    /// by `expire_transactions`.
+    pub fn get_pending_count(env: Env) -> u32 {
+        env.storage().instance().get(&PENDING_COUNT_KEY).unwrap_or(0)
+    }
+
+    /// Ids of the proposals counted by `get_pending_count`, in id order.
+    pub fn get_pending_transactions(env: Env) -> Vec<u32> {
+        env.storage().persistent().get(&PENDING_INDEX_KEY).unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Records which device backs the caller's seat, replacing any earlier entry.
+    pub fn register_signer_metadata(
+        env: Env,
+        caller: Address,
+        key_type: Symbol,
+        device_label: String,
+    ) -> Result<(), MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        if device_label.len() > MAX_DEVICE_LABEL_LEN {
+            return Err(MultisigError::InvalidMetadata);
+        }
+
+        let metadata = SignerMetadata {
+            key_type,
+            device_label,
+            registered_at: env.ledger().sequence(),
+        };
+        env.storage().persistent().set(&(SIGNER_META_KEY, caller.clone()), &metadata);
+
+        Self::publish_event(&env, (symbol_short!("signer_md"), caller), metadata);
+
+        Ok(())
+    }
+
+    pub fn get_owners_detailed(env: Env) -> Result<Vec<OwnerDetail>, MultisigError> {
+        let config = Self::get_config(&env)?;
+        let mut details = Vec::new(&env);
+        for owner in config.owners.iter() {
+            let metadata = match env.storage().persistent().get(&(SIGNER_META_KEY, owner.clone())) {
+                Some(metadata) => OwnerMetadata::Registered(metadata),
+                None => OwnerMetadata::Unregistered,
+            };
+            details.push_back(OwnerDetail { address: owner, metadata });
+        }
+
+        Ok(details)
+    }
+
+    /// Configuration version, bumped (with an `epoch_bump` event) whenever
+    /// the signer set or a threshold changes.
+    pub fn get_epoch(env: Env) -> Result<u32, MultisigError> {
+        Ok(Self::get_config(&env)?.epoch)
+    }
+
+    pub fn get_governance_info(env: Env) -> Result<GovernanceInfo, MultisigError> {
+        let config = Self::get_config(&env)?;
+        let mut weights = Vec::new(&env);
+        for owner in config.owners.iter() {
+            weights.push_back(Self::owner_weight(&config, &owner));
+        }
+
+        Ok(GovernanceInfo {
+            owners: config.owners,
+            weights,
+            required_approvals: config.required_approvals,
+            cancel_threshold: config.cancel_threshold,
+            epoch: config.epoch,
+        })
     }
 
     pub fn is_owner(env: Env, address: Address) -> Result<bool, MultisigError> {
@@ -281,15 +2155,306 @@ impl MultisigContract {
         Ok(config.owners.contains(&address))
     }
 
+    /// Membership of each candidate, in order.
+    pub fn are_owners(env: Env, addresses: Vec<Address>) -> Result<Vec<bool>, MultisigError> {
+        let config = Self::get_config(&env)?;
+        let mut result = Vec::new(&env);
+        for address in addresses.iter() {
+            result.push_back(config.owners.contains(&address));
+        }
+        Ok(result)
+    }
+
     pub fn get_approvals(
         env: Env,
         caller: Address,
         transaction_id: u32
     ) -> Result<Vec<Address>, MultisigError> {
         caller.require_auth();
-        Self::verify_owner(&env, &caller)?;
-        env.storage().persistent().get(&(APPROVAL_KEY, transaction_id))
-            .ok_or(MultisigError::TransactionNotFound)
+        Self::verify_viewer(&env, &caller)?;
+        Self::ensure_votes_kept(&env, transaction_id)?;
+        if !env.storage().persistent().has(&(TX_KEY, transaction_id)) {
+            return Err(MultisigError::TransactionNotFound);
+        }
+        let config = Self::get_config(&env)?;
+        Ok(Self::approvers(&env, &config, transaction_id))
+    }
+
+    /// Whether `owner` has approved `transaction_id`; touches only that
+    /// owner's approval entry.
+    pub fn has_approval(env: Env, caller: Address, transaction_id: u32, owner: Address) -> Result<bool, MultisigError> {
+        caller.require_auth();
+        Self::verify_viewer(&env, &caller)?;
+        Self::ensure_votes_kept(&env, transaction_id)?;
+        Ok(Self::has_approved(&env, transaction_id, &owner))
+    }
+
+    pub fn get_approval_record(
+        env: Env,
+        caller: Address,
+        transaction_id: u32,
+        owner: Address
+    ) -> Result<Option<ApprovalRecord>, MultisigError> {
+        caller.require_auth();
+        Self::verify_viewer(&env, &caller)?;
+        Self::ensure_votes_kept(&env, transaction_id)?;
+        Ok(Self::approval_record(&env, transaction_id, &owner))
+    }
+
+    pub fn get_proposal_lifetime(env: Env) -> Result<u32, MultisigError> {
+        Ok(Self::get_config(&env)?.proposal_lifetime)
+    }
+
+    /// Pays the next installment of a recurring payment once it is due.
+    /// Indexed amounts are converted at the oracle price current at this call.
+    /// Returns the amount of `token` transferred.
+    pub fn execute_recurring(env: Env, payment_id: u32) -> Result<i128, MultisigError> {
+        Self::ensure_not_locked_down(&env).map_err(|error| Self::multisig_error(&env, error))?;
+        let key = (RECURRING_KEY, payment_id);
+        let mut payment: RecurringPayment = env.storage().persistent().get(&key)
+            .ok_or(MultisigError::RecurringNotFound)?;
+
+        if env.ledger().sequence() < payment.next_due {
+            return Err(MultisigError::PaymentNotDue);
+        }
+
+        let amount = Self::resolve_amount(&env, &payment.token, &payment.amount)?;
+        Self::validate_transaction_inputs(&env, &payment.to, amount, &payment.token)?;
+        Self::check_savings_lock(&env, &payment.token, amount, false)?;
+        Self::check_reserved_balance(&env, &payment.token, amount)?;
+        Self::ensure_token_not_paused(&env, &payment.token)?;
+
+        payment.next_due = payment.next_due.checked_add(payment.interval)
+            .ok_or(MultisigError::ArithmeticError)?;
+        payment.payments_made = payment.payments_made.checked_add(1)
+            .ok_or(MultisigError::ArithmeticError)?;
+        env.storage().persistent().set(&key, &payment);
+
+        token::Client::new(&env, &payment.token)
+            .try_transfer(&env.current_contract_address(), &payment.to, &amount)
+            .map_err(|_| MultisigError::TokenTransferFailed)?
+            .map_err(|_| MultisigError::TokenTransferFailed)?;
+
+        Self::publish_event(
+            &env,
+            (symbol_short!("recurring"), payment_id),
+            (payment.to, amount, payment.token)
+        );
+
+        Ok(amount)
+    }
+
+    pub fn get_recurring(env: Env, payment_id: u32) -> Result<RecurringPayment, MultisigError> {
+        env.storage().persistent().get(&(RECURRING_KEY, payment_id))
+            .ok_or(MultisigError::RecurringNotFound)
+    }
+
+    pub fn get_savings_lock(env: Env, token: Address) -> Option<SavingsLock> {
+        env.storage().persistent().get(&(SAVINGS_KEY, token))
+    }
+
+    /// Balance of `token` that can currently be spent without touching savings.
+    pub fn get_spendable_balance(env: Env, token: Address) -> i128 {
+        let balance = token::Client::new(&env, &token).balance(&env.current_contract_address())
+            - Self::get_bonds_held(env.clone(), token.clone())
+            - Self::get_claims_held(env.clone(), token.clone());
+        match env.storage().persistent().get::<_, SavingsLock>(&(SAVINGS_KEY, token)) {
+            Some(lock) if env.ledger().sequence() < lock.unlock_ledger => (balance - lock.amount).max(0),
+            _ => balance.max(0),
+        }
+    }
+
+    pub fn get_trusted_recipient(env: Env, recipient: Address, token: Address) -> Option<TrustedRecipient> {
+        env.storage().persistent().get(&(TRUSTED_KEY, recipient, token))
+    }
+
+    pub fn get_bridge(env: Env) -> Result<Option<Address>, MultisigError> {
+        Ok(Self::get_config(&env)?.bridge)
+    }
+
+    pub fn get_max_whole_units(env: Env) -> Result<Option<u32>, MultisigError> {
+        Ok(Self::get_config(&env)?.max_whole_units)
+    }
+
+    pub fn is_submitter_auto_approve(env: Env) -> Result<bool, MultisigError> {
+        Ok(Self::get_config(&env)?.submitter_auto_approve)
+    }
+
+    pub fn is_dual_control(env: Env) -> Result<bool, MultisigError> {
+        Ok(Self::get_config(&env)?.dual_control)
+    }
+
+    pub fn get_token_policy(env: Env, token: Address) -> Option<TokenPolicy> {
+        env.storage().persistent().get(&(TOKEN_POLICY_KEY, token))
+    }
+
+    pub fn get_token_threshold(env: Env, token: Address) -> Option<u32> {
+        env.storage().persistent().get(&(TOKEN_THRESHOLD_KEY, token))
+    }
+
+    pub fn get_account_mode(env: Env) -> Result<AccountMode, MultisigError> {
+        Ok(Self::get_config(&env)?.account_mode)
+    }
+
+    pub fn get_retention_policy(env: Env) -> Result<RetentionPolicy, MultisigError> {
+        Ok(Self::get_config(&env)?.retention_policy)
+    }
+
+    pub fn get_duplicate_policy(env: Env) -> Result<DuplicatePolicy, MultisigError> {
+        Ok(Self::get_config(&env)?.duplicate_policy)
+    }
+
+    pub fn get_template(env: Env, name: Symbol) -> Result<TransferTemplate, MultisigError> {
+        env.storage().persistent().get(&(TEMPLATE_KEY, name))
+            .ok_or(MultisigError::TemplateNotFound)
+    }
+
+    pub fn get_rejections(
+        env: Env,
+        caller: Address,
+        transaction_id: u32
+    ) -> Result<Vec<Address>, MultisigError> {
+        caller.require_auth();
+        Self::verify_viewer(&env, &caller)?;
+        Self::ensure_votes_kept(&env, transaction_id)?;
+        Ok(env.storage().persistent().get(&(REJECTION_KEY, transaction_id))
+            .unwrap_or_else(|| Vec::new(&env)))
+    }
+
+    /// Reasons given for each rejection, in the same order as `get_rejections`.
+    pub fn get_rejection_reasons(
+        env: Env,
+        caller: Address,
+        transaction_id: u32
+    ) -> Result<Vec<RejectionReason>, MultisigError> {
+        caller.require_auth();
+        Self::verify_viewer(&env, &caller)?;
+        Self::ensure_votes_kept(&env, transaction_id)?;
+        Ok(env.storage().persistent().get(&(REJECTION_REASON_KEY, transaction_id))
+            .unwrap_or_else(|| Vec::new(&env)))
+    }
+
+}
+
+#[contractimpl]
+impl CustomAccountInterface for MultisigContract {
+    /// Owners co-signing the payload; each must authorize `(signature_payload,)`.
+    type Signature = Vec<Address>;
+    type Error = MultisigError;
+
+    #[allow(non_snake_case)]
+    fn __check_auth(
+        env: Env,
+        signature_payload: Hash<32>,
+        signatures: Vec<Address>,
+        auth_contexts: Vec<Context>,
+    ) -> Result<(), MultisigError> {
+        let config = Self::get_config(&env)?;
+        Self::ensure_not_locked_down(&env).map_err(|error| Self::multisig_error(&env, error))?;
+
+        // Entries the quorum approved in advance need no signatures, but are
+        // held to the same token rules. A quorum is not unanimity, so savings
+        // locks apply in full.
+        let payload: BytesN<32> = signature_payload.into();
+        if signatures.is_empty() {
+            for context in auth_contexts.iter() {
+                Self::check_context_policy(&env, &context, false)?;
+            }
+            return Self::consume_preauthorization(&env, &payload);
+        }
+
+        let AccountMode::Enabled(policy) = config.account_mode.clone() else {
+            return Err(MultisigError::AccountModeDisabled);
+        };
+
+        // Signatures count by the seat's vote weight, like approvals, and
+        // seats that cannot vote add nothing.
+        let mut seen = Map::new(&env);
+        let mut signed_weight = 0u32;
+        for signer in signatures.iter() {
+            if seen.contains_key(signer.clone()) {
+                return Err(MultisigError::DuplicateSigner);
+            }
+            if !config.owners.contains(&signer) {
+                return Err(MultisigError::Unauthorized);
+            }
+            signer.require_auth_for_args((payload.clone(),).into_val(&env));
+            if Self::owner_role(&config, &signer).can_vote() {
+                signed_weight = signed_weight.saturating_add(Self::owner_weight(&config, &signer));
+            }
+            seen.set(signer, true);
+        }
+
+        let unanimous = signed_weight >= Self::voting_weight(&config);
+        let mut required = 0;
+        for context in auth_contexts.iter() {
+            Self::check_context_policy(&env, &context, unanimous)?;
+            required = required.max(Self::context_threshold(&env, &policy, &context));
+        }
+
+        if signed_weight < required {
+            return Err(MultisigError::InsufficientSignatures);
+        }
+
+        Ok(())
+    }
+}
+
+impl MultisigContract {
+    /// Applies the token policy and savings lock of the called contract to
+    /// token-moving contexts, so the account path cannot move funds a proposal
+    /// would not be allowed to.
+    fn check_context_policy(env: &Env, context: &Context, unanimous: bool) -> Result<(), MultisigError> {
+        let Context::Contract(call) = context else {
+            return Ok(());
+        };
+
+        // Argument positions of (recipient, amount) in the SEP-41 token interface.
+        let (to_index, amount_index) = if call.fn_name == symbol_short!("transfer")
+            || call.fn_name == symbol_short!("approve")
+        {
+            (1, 2)
+        } else if call.fn_name == Symbol::new(env, "transfer_from") {
+            (2, 3)
+        } else {
+            return Ok(());
+        };
+
+        Self::ensure_token_not_paused(env, &call.contract)?;
+
+        let storage = env.storage().persistent();
+        if !storage.has(&(TOKEN_POLICY_KEY, call.contract.clone()))
+            && !storage.has(&(SAVINGS_KEY, call.contract.clone()))
+        {
+            return Ok(());
+        }
+
+        let to = call.args.get(to_index)
+            .and_then(|val| Address::try_from_val(env, &val).ok())
+            .ok_or(MultisigError::Unauthorized)?;
+        let amount = call.args.get(amount_index)
+            .and_then(|val| i128::try_from_val(env, &val).ok())
+            .ok_or(MultisigError::Unauthorized)?;
+
+        Self::check_token_policy(env, &call.contract, &to, amount)?;
+        Self::check_savings_lock(env, &call.contract, amount, unanimous)
     }
 
+    fn context_threshold(env: &Env, policy: &AccountAuthPolicy, context: &Context) -> u32 {
+        match context {
+            Context::Contract(call) => {
+                if call.fn_name == symbol_short!("transfer")
+                    || call.fn_name == Symbol::new(env, "transfer_from")
+                    || call.fn_name == symbol_short!("approve")
+                {
+                    policy.token_transfer
+                } else {
+                    policy.contract_call
+                }
+            },
+            Context::CreateContractHostFn(_) | Context::CreateContractWithCtorHostFn(_) => {
+                policy.create_contract
+            },
+        }
+    }
 }
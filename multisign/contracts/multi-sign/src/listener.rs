@@ -0,0 +1,68 @@
+//! Proposal listener. An optional contract, such as the companion indexer, is
+//! told about every proposal status change so it can keep query structures the
+//! wallet itself does not. A failing listener never blocks the wallet.
+
+use soroban_sdk::{contractclient, contractimpl, contracttype, symbol_short, Address, BytesN, Env, Symbol};
+
+use crate::{MultisigContract, MultisigContractArgs, MultisigContractClient, Transaction, TransactionStatus};
+
+pub(crate) const LISTENER_KEY: Symbol = symbol_short!("listener");
+
+/// What a listener learns about a proposal whenever its status changes, or
+/// its content changes while it is a draft.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalUpdate {
+    pub transaction_id: u32,
+    pub status: TransactionStatus,
+    pub submitter: Address,
+    pub to: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub data: BytesN<32>,
+}
+
+#[contractclient(name = "ProposalListenerClient")]
+pub trait ProposalListener {
+    /// Called by the wallet, which is authenticated as the direct invoker.
+    fn on_proposal(env: Env, wallet: Address, update: ProposalUpdate);
+}
+
+impl MultisigContract {
+    pub(crate) fn set_listener(env: &Env, listener: Option<Address>) {
+        match &listener {
+            Some(listener) => env.storage().persistent().set(&LISTENER_KEY, listener),
+            None => env.storage().persistent().remove(&LISTENER_KEY),
+        }
+        Self::publish_event(env, (symbol_short!("lstn_set"),), listener);
+    }
+
+    pub(crate) fn notify_listener(env: &Env, transaction_id: u32, transaction: &Transaction) {
+        let Some(listener) = Self::get_listener(env.clone()) else {
+            return;
+        };
+
+        let update = ProposalUpdate {
+            transaction_id,
+            status: transaction.status,
+            submitter: transaction.submitter.clone(),
+            to: transaction.to.clone(),
+            token: transaction.token.clone(),
+            amount: transaction.amount,
+            data: transaction.data.clone(),
+        };
+        let delivered = ProposalListenerClient::new(env, &listener)
+            .try_on_proposal(&env.current_contract_address(), &update)
+            .is_ok();
+        if !delivered {
+            Self::publish_event(env, (symbol_short!("lstn_fail"), transaction_id), listener);
+        }
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    pub fn get_listener(env: Env) -> Option<Address> {
+        env.storage().persistent().get(&LISTENER_KEY)
+    }
+}
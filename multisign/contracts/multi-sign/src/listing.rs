@@ -0,0 +1,68 @@
+//! Paged proposal listing, so a front-end can render the queue or the
+//! history in a few calls instead of one `get_transaction` per id.
+
+use soroban_sdk::{contractimpl, contracttype, Address, Env, Vec};
+
+use crate::{
+    MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError, Transaction, TransactionStatus,
+    TX_KEY,
+};
+
+const MAX_LIST_LIMIT: u32 = 50;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TxFilter {
+    All,
+    /// Still open for votes; proposals past their deadline are left out.
+    Pending,
+    Executed,
+    Cancelled,
+    /// Closed as expired, or pending past their deadline.
+    Expired,
+}
+
+impl MultisigContract {
+    fn matches_filter(env: &Env, transaction: &Transaction, filter: TxFilter) -> bool {
+        let overdue = transaction.status == TransactionStatus::Pending && Self::is_overdue(env, transaction);
+        match filter {
+            TxFilter::All => true,
+            TxFilter::Pending => transaction.status == TransactionStatus::Pending && !overdue,
+            TxFilter::Executed => transaction.status == TransactionStatus::Executed,
+            TxFilter::Cancelled => transaction.status == TransactionStatus::Cancelled,
+            TxFilter::Expired => transaction.status == TransactionStatus::Expired || overdue,
+        }
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Up to `limit` (at most 50) proposals matching `filter`, with id
+    /// `start` or above, in id order. Continue from one past the last id
+    /// returned. Open to the same viewers as `get_transaction`.
+    pub fn get_transactions(
+        env: Env,
+        caller: Address,
+        start: u32,
+        limit: u32,
+        filter: TxFilter,
+    ) -> Result<Vec<(u32, Transaction)>, MultisigError> {
+        caller.require_auth();
+        Self::verify_viewer(&env, &caller)?;
+
+        let transaction_count = Self::transaction_count(&env);
+        let limit = limit.min(MAX_LIST_LIMIT);
+        let mut transactions = Vec::new(&env);
+        let mut id = start.max(1);
+        while id <= transaction_count && transactions.len() < limit {
+            if let Some(transaction) = env.storage().persistent().get::<_, Transaction>(&(TX_KEY, id)) {
+                if Self::matches_filter(&env, &transaction, filter) {
+                    transactions.push_back((id, transaction));
+                }
+            }
+            id += 1;
+        }
+
+        Ok(transactions)
+    }
+}
@@ -0,0 +1,105 @@
+//! Emergency lockdown. Any single owner can freeze every execution path at
+//! once while voting carries on. Lifting it takes an approved `LiftLockdown`
+//! proposal, and execution resumes only after the configured cooldown. A
+//! pause freezes these paths too, reporting `Paused`.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, Env, Error, Symbol};
+
+use crate::{
+    ConfigChange, ExtendedError, MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError,
+    TransactionKind,
+};
+
+pub(crate) const LOCKDOWN_KEY: Symbol = symbol_short!("lockdown");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LockdownState {
+    pub triggered_by: Address,
+    pub triggered_at: u32,
+    /// Set once an unlock has been approved; execution resumes at this ledger.
+    pub unlocks_at: Option<u32>,
+}
+
+impl MultisigContract {
+    pub(crate) fn ensure_not_locked_down(env: &Env) -> Result<(), Error> {
+        if Self::is_paused(env.clone()) {
+            return Err(ExtendedError::Paused.into());
+        }
+        if Self::is_locked_down(env.clone()) {
+            return Err(MultisigError::LockedDown.into());
+        }
+
+        Ok(())
+    }
+
+    /// Whether `kind` lifts a lockdown, and so is exempt from it.
+    pub(crate) fn lifts_lockdown(kind: &TransactionKind) -> bool {
+        matches!(kind, TransactionKind::Config(ConfigChange::LiftLockdown))
+    }
+
+    /// Rejects executing `kind` under a freeze it does not lift. An `Unpause`
+    /// still runs under a lockdown while the wallet is paused, as no
+    /// `LiftLockdown` can be proposed until the pause is gone.
+    pub(crate) fn ensure_not_frozen(env: &Env, kind: &TransactionKind) -> Result<(), Error> {
+        let paused = Self::is_paused(env.clone());
+        if paused && !Self::lifts_pause(kind) {
+            return Err(ExtendedError::Paused.into());
+        }
+        if Self::is_locked_down(env.clone()) && !Self::lifts_lockdown(kind) && !paused {
+            return Err(MultisigError::LockedDown.into());
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn begin_unlock(env: &Env, cooldown: u32) -> Result<(), MultisigError> {
+        if !Self::is_locked_down(env.clone()) {
+            return Err(MultisigError::NotLockedDown);
+        }
+        let mut state: LockdownState = env.storage().persistent().get(&LOCKDOWN_KEY)
+            .ok_or(MultisigError::NotLockedDown)?;
+
+        let unlocks_at = env.ledger().sequence().checked_add(cooldown)
+            .ok_or(MultisigError::ArithmeticError)?;
+        state.unlocks_at = Some(unlocks_at);
+        env.storage().persistent().set(&LOCKDOWN_KEY, &state);
+
+        Self::publish_event(env, (symbol_short!("unlocking"),), unlocks_at);
+
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Freezes execution immediately. Triggering it again during an unlock
+    /// cooldown cancels the unlock.
+    pub fn lockdown(env: Env, caller: Address) -> Result<(), MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        let state = LockdownState {
+            triggered_by: caller.clone(),
+            triggered_at: env.ledger().sequence(),
+            unlocks_at: None,
+        };
+        env.storage().persistent().set(&LOCKDOWN_KEY, &state);
+
+        Self::publish_event(&env, (symbol_short!("lockdown"),), caller);
+
+        Ok(())
+    }
+
+    pub fn is_locked_down(env: Env) -> bool {
+        match env.storage().persistent().get::<_, LockdownState>(&LOCKDOWN_KEY) {
+            Some(state) => state.unlocks_at.is_none_or(|unlocks_at| env.ledger().sequence() < unlocks_at),
+            None => false,
+        }
+    }
+
+    /// The most recent lockdown, including one whose cooldown has passed.
+    pub fn get_lockdown(env: Env) -> Option<LockdownState> {
+        env.storage().persistent().get(&LOCKDOWN_KEY)
+    }
+}
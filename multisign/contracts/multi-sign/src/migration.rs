@@ -0,0 +1,169 @@
+//! Paged export of the wallet's state, for off-chain backup, and the matching
+//! one-time import that lets pending proposals survive a move to a new wallet.
+//! Only the factory named as importer when the new wallet was deployed may
+//! run the import, and only before the wallet is set up.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+use crate::{
+    MultisigConfig, MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError,
+    RejectionReason, Transaction, TransactionKind, TransactionStatus, CONFIG_KEY, CONTENT_KEY,
+    PENDING_COUNT_KEY, REJECTION_KEY, REJECTION_REASON_KEY, TX_COUNT_KEY, TX_KEY,
+};
+
+const MAX_EXPORT_LIMIT: u32 = 50;
+/// The factory a wallet was deployed by to receive a migration.
+const IMPORTER_KEY: Symbol = symbol_short!("importer");
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ExportedTransaction {
+    pub id: u32,
+    pub transaction: Transaction,
+    pub approvals: Vec<Address>,
+    pub rejections: Vec<Address>,
+    /// Reason for each rejection, in the same order.
+    pub rejection_reasons: Vec<RejectionReason>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct StateExport {
+    pub config: MultisigConfig,
+    /// Highest proposal id issued so far.
+    pub transaction_count: u32,
+    /// Pending proposals with ids from the cursor upwards, in id order.
+    pub transactions: Vec<ExportedTransaction>,
+    /// Cursor for the next page, or `None` once every proposal has been scanned.
+    pub next_cursor: Option<u32>,
+}
+
+impl MultisigContract {
+    /// Reserves an undeployed wallet for `import_state` by `importer`.
+    pub(crate) fn reserve_for_import(env: &Env, importer: &Address) {
+        env.storage().instance().set(&IMPORTER_KEY, importer);
+    }
+
+    /// Whether the wallet awaits an import, and so cannot be initialized.
+    pub(crate) fn awaits_import(env: &Env) -> bool {
+        env.storage().instance().has(&IMPORTER_KEY)
+    }
+
+    /// Combined weight of `voters`, which must all be owners.
+    fn vote_weight(config: &MultisigConfig, voters: &Vec<Address>) -> Result<u32, MultisigError> {
+        let mut weight = 0u32;
+        for voter in voters.iter() {
+            if !config.owners.contains(&voter) {
+                return Err(MultisigError::InvalidMetadata);
+            }
+            weight = weight.checked_add(Self::owner_weight(config, &voter))
+                .ok_or(MultisigError::ArithmeticError)?;
+        }
+        Ok(weight)
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Exports the config and up to `limit` (at most 50) pending proposals with
+    /// id `cursor` or above. Start from cursor 0 and follow `next_cursor`.
+    /// Open to the same viewers as `get_transaction`.
+    pub fn export_state(env: Env, caller: Address, cursor: u32, limit: u32) -> Result<StateExport, MultisigError> {
+        caller.require_auth();
+        Self::verify_viewer(&env, &caller)?;
+        let config = Self::get_config(&env)?;
+        let transaction_count = Self::transaction_count(&env);
+        let limit = limit.min(MAX_EXPORT_LIMIT);
+
+        let mut transactions = Vec::new(&env);
+        let mut id = cursor.max(1);
+        while id <= transaction_count && transactions.len() < limit {
+            if let Some(transaction) = env.storage().persistent().get::<_, Transaction>(&(TX_KEY, id)) {
+                if transaction.status == TransactionStatus::Pending && !Self::is_overdue(&env, &transaction) {
+                    transactions.push_back(ExportedTransaction {
+                        id,
+                        transaction,
+                        approvals: Self::approvers(&env, &config, id),
+                        rejections: env.storage().persistent().get(&(REJECTION_KEY, id))
+                            .unwrap_or_else(|| Vec::new(&env)),
+                        rejection_reasons: env.storage().persistent().get(&(REJECTION_REASON_KEY, id))
+                            .unwrap_or_else(|| Vec::new(&env)),
+                    });
+                }
+            }
+            id += 1;
+        }
+
+        let next_cursor = if id <= transaction_count { Some(id) } else { None };
+
+        Ok(StateExport { config, transaction_count, transactions, next_cursor })
+    }
+
+    /// Seeds an uninitialized wallet from a predecessor's export, in place of
+    /// `initialize`. Only the importer named at deployment may call it, and
+    /// every owner in the imported config must authorize it too. Proposals
+    /// keep their ids, each of which may appear once; merge all export pages
+    /// into one snapshot first, since the wallet is live as soon as this
+    /// returns.
+    pub fn import_state(env: Env, snapshot: StateExport) -> Result<(), MultisigError> {
+        if env.storage().persistent().has(&CONFIG_KEY) {
+            return Err(MultisigError::AlreadyInitialized);
+        }
+        let importer: Address = env.storage().instance().get(&IMPORTER_KEY)
+            .ok_or(MultisigError::Unauthorized)?;
+        importer.require_auth();
+
+        let config = snapshot.config;
+        let mut transaction_count = snapshot.transaction_count;
+        Self::validate_owner_set(&env, &config)?;
+        Self::validate_threshold(&config, config.cancel_threshold)?;
+        for owner in config.owners.iter() {
+            owner.require_auth();
+        }
+
+        for exported in snapshot.transactions.iter() {
+            let transaction = exported.transaction;
+            if transaction.status != TransactionStatus::Pending
+                || transaction.approvals != Self::vote_weight(&config, &exported.approvals)?
+                || transaction.rejections != Self::vote_weight(&config, &exported.rejections)?
+                || exported.rejection_reasons.len() != exported.rejections.len()
+            {
+                return Err(MultisigError::InvalidMetadata);
+            }
+
+            let id = exported.id;
+            if env.storage().persistent().has(&(TX_KEY, id)) {
+                return Err(MultisigError::InvalidMetadata);
+            }
+            if transaction.kind == TransactionKind::Transfer {
+                let hash = Self::content_hash(&env, &transaction.to, transaction.amount, &transaction.token, &transaction.data);
+                env.storage().persistent().set(&(CONTENT_KEY, hash), &id);
+            }
+            env.storage().persistent().set(&(TX_KEY, id), &transaction);
+            for approver in exported.approvals.iter() {
+                Self::store_approval(&env, id, &approver, &transaction);
+            }
+            env.storage().persistent().set(&(REJECTION_KEY, id), &exported.rejections);
+            env.storage().persistent().set(&(REJECTION_REASON_KEY, id), &exported.rejection_reasons);
+            transaction_count = transaction_count.max(id);
+            Self::track_pending(&env, id, true);
+        }
+
+        env.storage().persistent().set(&CONFIG_KEY, &config);
+        env.storage().instance().remove(&IMPORTER_KEY);
+        env.storage().instance().set(&TX_COUNT_KEY, &transaction_count);
+        env.storage().instance().set(&PENDING_COUNT_KEY, &snapshot.transactions.len());
+        Self::extend_core_ttl(&env);
+        for owner in config.owners.iter() {
+            Self::record_activity(&env, &owner);
+        }
+
+        Self::publish_event(
+            &env,
+            (symbol_short!("import"), config.owners.len(), config.required_approvals),
+            snapshot.transactions.len()
+        );
+
+        Ok(())
+    }
+}
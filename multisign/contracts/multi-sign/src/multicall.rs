@@ -0,0 +1,37 @@
+//! Batched owner housekeeping: one signed call carrying several votes and
+//! cancellations by the same owner. Anything that moves funds or changes
+//! configuration stays out of reach here.
+
+use soroban_sdk::{contractimpl, contracttype, Address, Env, Vec};
+
+use crate::{MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError, RejectionReason};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OwnerAction {
+    Approve(u32),
+    Reject(u32, RejectionReason),
+    Cancel(u32),
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Applies `actions` in order on behalf of `caller`, who authorizes once.
+    /// The first failing action aborts the whole batch.
+    pub fn multicall(env: Env, caller: Address, actions: Vec<OwnerAction>) -> Result<u32, MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        for action in actions.iter() {
+            match action {
+                OwnerAction::Approve(transaction_id) => Self::record_approval(&env, &caller, transaction_id)?,
+                OwnerAction::Reject(transaction_id, reason) => {
+                    Self::record_rejection(&env, &caller, transaction_id, reason)?
+                },
+                OwnerAction::Cancel(transaction_id) => Self::cancel_pending(&env, &caller, transaction_id)?,
+            }
+        }
+
+        Ok(actions.len())
+    }
+}
@@ -0,0 +1,56 @@
+//! Native XLM. The wallet resolves the Stellar Asset Contract for lumens
+//! itself, so owners and depositors can move and query XLM without knowing
+//! its SAC address.
+
+use soroban_sdk::{contractimpl, symbol_short, token, Address, Bytes, BytesN, Env};
+
+use crate::{MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError};
+
+/// XDR encoding of `Asset::Native`.
+const NATIVE_ASSET_XDR: [u8; 4] = [0, 0, 0, 0];
+
+#[contractimpl]
+impl MultisigContract {
+    /// Address of the native XLM SAC on this network.
+    pub fn get_native_token(env: Env) -> Address {
+        env.deployer().with_stellar_asset(Bytes::from_array(&env, &NATIVE_ASSET_XDR)).deployed_address()
+    }
+
+    /// The wallet's XLM balance, in stroops.
+    pub fn get_native_balance(env: Env) -> i128 {
+        token::Client::new(&env, &Self::get_native_token(env.clone())).balance(&env.current_contract_address())
+    }
+
+    /// Moves `amount` stroops of XLM from `from` into the wallet and returns
+    /// the wallet's new XLM balance. Anyone may deposit.
+    pub fn deposit(env: Env, from: Address, amount: i128) -> Result<i128, MultisigError> {
+        from.require_auth();
+        if amount <= 0 {
+            return Err(MultisigError::InvalidAmount);
+        }
+        if from == env.current_contract_address() {
+            return Err(MultisigError::InvalidAddress);
+        }
+
+        token::Client::new(&env, &Self::get_native_token(env.clone()))
+            .try_transfer(&from, &env.current_contract_address(), &amount)
+            .map_err(|_| MultisigError::TokenTransferFailed)?
+            .map_err(|_| MultisigError::TokenTransferFailed)?;
+
+        Self::publish_event(&env, (symbol_short!("deposit"), from), amount);
+
+        Ok(Self::get_native_balance(env))
+    }
+
+    /// Like `submit_transaction`, paying `amount` stroops of XLM.
+    pub fn submit_native_transfer(
+        env: Env,
+        caller: Address,
+        to: Address,
+        amount: i128,
+        data: BytesN<32>,
+    ) -> Result<u32, MultisigError> {
+        let native = Self::get_native_token(env.clone());
+        Self::submit_transaction(env, caller, to, amount, native, data)
+    }
+}
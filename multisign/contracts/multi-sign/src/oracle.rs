@@ -0,0 +1,25 @@
+//! Client side of the SEP-40 price feed interface (as served by e.g. Reflector).
+
+use soroban_sdk::{contractclient, contracttype, Address, Env, Symbol};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OracleAsset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    /// Price in the oracle's base asset, scaled by `10^decimals()`.
+    pub price: i128,
+    /// Unix timestamp of the observation, in seconds.
+    pub timestamp: u64,
+}
+
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracle {
+    fn lastprice(env: Env, asset: OracleAsset) -> Option<PriceData>;
+    fn decimals(env: Env) -> u32;
+}
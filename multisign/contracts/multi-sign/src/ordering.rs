@@ -0,0 +1,87 @@
+//! Strict ordering. When enabled, a proposal can only execute once every
+//! proposal submitted before it has been executed, cancelled or has expired, so
+//! a runbook written as a sequence of proposals runs in the order it was filed.
+//! Drafts take no part until they are opened.
+
+use soroban_sdk::{contractimpl, symbol_short, Env, Error, Symbol};
+
+use crate::{
+    ExtendedError, MultisigContract, MultisigContractArgs, MultisigContractClient, Transaction, TransactionStatus,
+    TX_KEY,
+};
+
+pub(crate) const STRICT_ORDER_KEY: Symbol = symbol_short!("fifo");
+/// Lowest proposal id that may still hold up later ones.
+pub(crate) const ORDER_HEAD_KEY: Symbol = symbol_short!("fifo_head");
+
+impl MultisigContract {
+    pub(crate) fn set_strict_ordering(env: &Env, enabled: bool) {
+        env.storage().persistent().set(&STRICT_ORDER_KEY, &enabled);
+        Self::publish_event(env, (symbol_short!("fifo"),), enabled);
+    }
+
+    /// The earliest proposal before `transaction_id` that is still open, and
+    /// the head moved past those settled for good.
+    fn ordering_blocker(env: &Env, transaction_id: u32) -> (Option<u32>, u32) {
+        let start: u32 = env.storage().persistent().get(&ORDER_HEAD_KEY).unwrap_or(1);
+        let mut head = start;
+        for id in start..transaction_id {
+            match env.storage().persistent().get::<_, Transaction>(&(TX_KEY, id)) {
+                Some(transaction) if transaction.status == TransactionStatus::Pending
+                    && !Self::is_overdue(env, &transaction) =>
+                {
+                    return (Some(id), head);
+                },
+                // A draft holds nothing up yet, but may still be opened.
+                Some(transaction) if transaction.status == TransactionStatus::Draft => continue,
+                _ => {},
+            }
+            if id == head {
+                head += 1;
+            }
+        }
+
+        (None, head)
+    }
+
+    /// Fails with `OutOfOrder` while strict ordering is on and an earlier
+    /// proposal is still open.
+    pub(crate) fn ensure_in_order(env: &Env, transaction_id: u32) -> Result<(), Error> {
+        if !Self::is_strict_ordering(env.clone()) {
+            return Ok(());
+        }
+
+        match Self::ordering_blocker(env, transaction_id).0 {
+            Some(_) => Err(ExtendedError::OutOfOrder.into()),
+            None => Ok(()),
+        }
+    }
+
+    /// Moves the head past `transaction_id` and everything settled before it,
+    /// once it has executed.
+    pub(crate) fn advance_order_head(env: &Env, transaction_id: u32) {
+        if !Self::is_strict_ordering(env.clone()) {
+            return;
+        }
+
+        let (_, head) = Self::ordering_blocker(env, transaction_id + 1);
+        env.storage().persistent().set(&ORDER_HEAD_KEY, &head);
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    pub fn is_strict_ordering(env: Env) -> bool {
+        env.storage().persistent().get(&STRICT_ORDER_KEY).unwrap_or(false)
+    }
+
+    /// The earliest proposal that must settle before `transaction_id` can
+    /// execute, if strict ordering is on.
+    pub fn get_ordering_blocker(env: Env, transaction_id: u32) -> Option<u32> {
+        if !Self::is_strict_ordering(env.clone()) {
+            return None;
+        }
+
+        Self::ordering_blocker(&env, transaction_id).0
+    }
+}
@@ -0,0 +1,401 @@
+//! Owner seats beyond bare membership: vote weights, roles and labels, the
+//! structured `initialize_with_owners` that sets them all up in one call, and
+//! adding, removing or re-keying seats by proposal.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, Env, Map, String, Symbol, Vec};
+
+use crate::{
+    ConfigChange, MultisigConfig, MultisigContract, MultisigContractArgs, MultisigContractClient,
+    ExtendedChange, MultisigError, RejectionReason, Transaction, CONFIG_KEY, REJECTION_KEY, REJECTION_REASON_KEY,
+    TX_KEY,
+};
+
+pub(crate) const OWNER_LABEL_KEY: Symbol = symbol_short!("own_label");
+
+const MAX_OWNER_LABEL_LEN: u32 = 64;
+
+/// What an owner seat may do. Every seat may cancel its own proposals.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OwnerRole {
+    /// Submits, votes on and executes proposals.
+    Full,
+    /// Only submits proposals.
+    Proposer,
+    /// Only approves or rejects proposals.
+    Approver,
+    /// Only executes approved proposals.
+    Executor,
+}
+
+impl OwnerRole {
+    pub fn can_propose(&self) -> bool {
+        matches!(self, OwnerRole::Full | OwnerRole::Proposer)
+    }
+
+    pub fn can_vote(&self) -> bool {
+        matches!(self, OwnerRole::Full | OwnerRole::Approver)
+    }
+
+    pub fn can_execute(&self) -> bool {
+        matches!(self, OwnerRole::Full | OwnerRole::Executor)
+    }
+}
+
+/// Key rotation for a seat: `new` takes over `old`'s position, weight,
+/// role and label.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OwnerReplacement {
+    pub old: Address,
+    pub new: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OwnerInit {
+    pub address: Address,
+    /// Votes the owner's approval or rejection counts for; at least 1.
+    pub weight: u32,
+    pub role: OwnerRole,
+    /// Human-readable name of the seat, e.g. "CFO"; may be empty.
+    pub label: String,
+}
+
+impl MultisigContract {
+    /// Seats without an entry in `config.weights` carry one vote.
+    pub(crate) fn owner_weight(config: &MultisigConfig, owner: &Address) -> u32 {
+        config.weights.get(owner.clone()).unwrap_or(1)
+    }
+
+    /// Seats without an entry in `config.roles` have every role.
+    pub(crate) fn owner_role(config: &MultisigConfig, owner: &Address) -> OwnerRole {
+        config.roles.get(owner.clone()).unwrap_or(OwnerRole::Full)
+    }
+
+    /// Combined weight of the seats that may vote.
+    pub(crate) fn voting_weight(config: &MultisigConfig) -> u32 {
+        config.owners.iter()
+            .filter(|owner| Self::owner_role(config, owner).can_vote())
+            .map(|owner| Self::owner_weight(config, &owner))
+            .fold(0u32, u32::saturating_add)
+    }
+
+    pub(crate) fn ensure_role(
+        config: &MultisigConfig,
+        caller: &Address,
+        permitted: fn(&OwnerRole) -> bool,
+    ) -> Result<(), MultisigError> {
+        if !permitted(&Self::owner_role(config, caller)) {
+            return Err(MultisigError::Unauthorized);
+        }
+
+        Ok(())
+    }
+
+    /// Moves a seat's weight and role to the address taking it over.
+    pub(crate) fn transfer_seat(config: &mut MultisigConfig, from: &Address, to: &Address) {
+        if let Some(weight) = config.weights.get(from.clone()) {
+            config.weights.remove(from.clone());
+            config.weights.set(to.clone(), weight);
+        }
+        if let Some(role) = config.roles.get(from.clone()) {
+            config.roles.remove(from.clone());
+            config.roles.set(to.clone(), role);
+        }
+    }
+
+    /// Adds a full seat with one vote for `owner`.
+    pub(crate) fn seat_owner(env: &Env, config: &mut MultisigConfig, owner: &Address) -> Result<(), MultisigError> {
+        if Self::is_unusable_address(env, owner) {
+            return Err(MultisigError::InvalidAddress);
+        }
+        if config.owners.contains(owner) {
+            return Err(MultisigError::DuplicateOwner);
+        }
+        config.owners.push_back(owner.clone());
+
+        Ok(())
+    }
+
+    /// Fails unless the seats can still propose, reach both thresholds and
+    /// execute between them.
+    fn ensure_seats_workable(config: &MultisigConfig) -> Result<(), MultisigError> {
+        let voting = Self::voting_weight(config);
+        let can_propose = config.owners.iter().any(|seat| Self::owner_role(config, &seat).can_propose());
+        let can_execute = config.owners.iter().any(|seat| Self::owner_role(config, &seat).can_execute());
+        if voting < config.required_approvals || voting < config.cancel_threshold || !can_propose || !can_execute {
+            return Err(MultisigError::InvalidThreshold);
+        }
+
+        Ok(())
+    }
+
+    /// Removes `owner`'s seat, failing if the remaining seats could no longer
+    /// propose, reach either threshold or execute.
+    pub(crate) fn unseat_owner(config: &mut MultisigConfig, owner: &Address) -> Result<(), MultisigError> {
+        let index = config.owners.first_index_of(owner).ok_or(MultisigError::InvalidOwner)?;
+        config.owners.remove(index);
+        config.weights.remove(owner.clone());
+        config.roles.remove(owner.clone());
+
+        Self::ensure_seats_workable(config)
+    }
+
+    pub(crate) fn add_owner(env: &Env, owner: Address) -> Result<(), MultisigError> {
+        let mut config = Self::get_config(env)?;
+        Self::seat_owner(env, &mut config, &owner)?;
+        Self::bump_epoch(env, &mut config)?;
+        env.storage().persistent().set(&CONFIG_KEY, &config);
+        Self::record_activity(env, &owner);
+
+        Self::publish_event(env, (symbol_short!("owner_add"),), owner);
+        Self::refresh_quorum_alarm(env)?;
+
+        Ok(())
+    }
+
+    /// Withdraws `owner`'s approvals, worth `weight`, from every pending
+    /// proposal. Walks the pending index, so the cost does not grow with the
+    /// wallet's history.
+    pub(crate) fn void_pending_approvals(env: &Env, config: &MultisigConfig, owner: &Address, weight: u32) {
+        for id in Self::get_pending_transactions(env.clone()).iter() {
+            if !Self::has_approved(env, id, owner) {
+                continue;
+            }
+            let Some(mut transaction) = env.storage().persistent().get::<_, Transaction>(&(TX_KEY, id)) else {
+                continue;
+            };
+            Self::withdraw_approval(env, config, id, &mut transaction, owner, weight);
+            Self::publish_event(env, (symbol_short!("appr_void"), id), owner.clone());
+        }
+    }
+
+    /// Withdraws `owner`'s rejections, with their reasons, from every pending
+    /// proposal, and recounts each tally from the rejectors left. For a seat
+    /// that is leaving, so it cannot count again under another key.
+    pub(crate) fn void_pending_rejections(env: &Env, config: &MultisigConfig, owner: &Address) {
+        for id in Self::get_pending_transactions(env.clone()).iter() {
+            let mut rejectors: Vec<Address> = env.storage().persistent().get(&(REJECTION_KEY, id))
+                .unwrap_or_else(|| Vec::new(env));
+            let Some(index) = rejectors.first_index_of(owner) else {
+                continue;
+            };
+            let Some(mut transaction) = env.storage().persistent().get::<_, Transaction>(&(TX_KEY, id)) else {
+                continue;
+            };
+            rejectors.remove(index);
+            let mut reasons: Vec<RejectionReason> = env.storage().persistent().get(&(REJECTION_REASON_KEY, id))
+                .unwrap_or_else(|| Vec::new(env));
+            reasons.remove(index);
+
+            transaction.rejections = rejectors.iter()
+                .map(|rejector| Self::owner_weight(config, &rejector))
+                .fold(0u32, u32::saturating_add);
+            env.storage().persistent().set(&(TX_KEY, id), &transaction);
+            env.storage().persistent().set(&(REJECTION_KEY, id), &rejectors);
+            env.storage().persistent().set(&(REJECTION_REASON_KEY, id), &reasons);
+            Self::publish_event(env, (symbol_short!("rej_void"), id), owner.clone());
+        }
+    }
+
+    /// Removes `owner`'s seat and withdraws their approvals and rejections
+    /// from every pending proposal.
+    pub(crate) fn remove_owner(env: &Env, owner: Address) -> Result<(), MultisigError> {
+        let mut config = Self::get_config(env)?;
+        let weight = Self::owner_weight(&config, &owner);
+        Self::unseat_owner(&mut config, &owner)?;
+        Self::bump_epoch(env, &mut config)?;
+        env.storage().persistent().set(&CONFIG_KEY, &config);
+        env.storage().persistent().remove(&(OWNER_LABEL_KEY, owner.clone()));
+        Self::forget_signer_key(env, &owner);
+
+        Self::void_pending_approvals(env, &config, &owner, weight);
+        Self::void_pending_rejections(env, &config, &owner);
+
+        Self::publish_event(env, (symbol_short!("owner_rm"),), owner);
+        Self::refresh_quorum_alarm(env)?;
+
+        Ok(())
+    }
+
+    /// Swaps the key behind a seat in one step, so the owner count and
+    /// thresholds never change. Approvals and rejections the old key gave on
+    /// pending proposals are withdrawn.
+    pub(crate) fn replace_owner(env: &Env, replacement: OwnerReplacement) -> Result<(), MultisigError> {
+        let OwnerReplacement { old, new } = replacement;
+        if Self::is_unusable_address(env, &new) {
+            return Err(MultisigError::InvalidAddress);
+        }
+        Self::replace_seat(env, &old, &new)?;
+        if let Some(label) = Self::get_owner_label(env.clone(), old.clone()) {
+            env.storage().persistent().remove(&(OWNER_LABEL_KEY, old.clone()));
+            env.storage().persistent().set(&(OWNER_LABEL_KEY, new.clone()), &label);
+        }
+        Self::publish_event(env, (symbol_short!("owner_rep"), old), new);
+
+        Ok(())
+    }
+
+    /// Changes how many votes `owner`'s seat carries. Their approvals on
+    /// pending proposals are withdrawn, so each is given again at the new
+    /// weight; their rejections are recounted in place.
+    pub(crate) fn set_owner_weight(env: &Env, owner: Address, weight: u32) -> Result<(), MultisigError> {
+        let mut config = Self::get_config(env)?;
+        if !config.owners.contains(&owner) {
+            return Err(MultisigError::InvalidOwner);
+        }
+        if weight == 0 {
+            return Err(MultisigError::InvalidThreshold);
+        }
+        let previous = Self::owner_weight(&config, &owner);
+        if weight == 1 {
+            config.weights.remove(owner.clone());
+        } else {
+            config.weights.set(owner.clone(), weight);
+        }
+        let remaining = Self::voting_weight(&config);
+        if remaining < config.required_approvals || remaining < config.cancel_threshold {
+            return Err(MultisigError::InvalidThreshold);
+        }
+        Self::bump_epoch(env, &mut config)?;
+        env.storage().persistent().set(&CONFIG_KEY, &config);
+
+        for id in Self::get_pending_transactions(env.clone()).iter() {
+            let Some(mut transaction) = env.storage().persistent().get::<_, Transaction>(&(TX_KEY, id)) else {
+                continue;
+            };
+            let rejectors: Vec<Address> = env.storage().persistent().get(&(REJECTION_KEY, id))
+                .unwrap_or_else(|| Vec::new(env));
+            if rejectors.contains(&owner) {
+                transaction.rejections = transaction.rejections.saturating_sub(previous).saturating_add(weight);
+                env.storage().persistent().set(&(TX_KEY, id), &transaction);
+            }
+            if Self::has_approved(env, id, &owner) {
+                Self::withdraw_approval(env, &config, id, &mut transaction, &owner, previous);
+                Self::publish_event(env, (symbol_short!("appr_void"), id), owner.clone());
+            }
+        }
+
+        Self::publish_event(env, (symbol_short!("owner_wt"), owner), (previous, weight));
+        Self::refresh_quorum_alarm(env)?;
+
+        Ok(())
+    }
+
+    /// Restricts `owner`'s seat to `role`. The seats must still be able to
+    /// propose, reach both thresholds and execute between them. A seat that
+    /// loses its vote has its approvals on pending proposals withdrawn.
+    pub(crate) fn set_owner_role(env: &Env, owner: Address, role: OwnerRole) -> Result<(), MultisigError> {
+        let mut config = Self::get_config(env)?;
+        if !config.owners.contains(&owner) {
+            return Err(MultisigError::InvalidOwner);
+        }
+        let previous = Self::owner_role(&config, &owner);
+        if role == OwnerRole::Full {
+            config.roles.remove(owner.clone());
+        } else {
+            config.roles.set(owner.clone(), role);
+        }
+        Self::ensure_seats_workable(&config)?;
+        Self::bump_epoch(env, &mut config)?;
+        env.storage().persistent().set(&CONFIG_KEY, &config);
+
+        if previous.can_vote() && !role.can_vote() {
+            let weight = Self::owner_weight(&config, &owner);
+            Self::void_pending_approvals(env, &config, &owner, weight);
+        }
+
+        Self::publish_event(env, (symbol_short!("owner_rol"), owner), (previous, role));
+        Self::refresh_quorum_alarm(env)?;
+
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Like `initialize`, with each owner's weight, role and label given up
+    /// front. `required_approvals` is a sum of weights.
+    pub fn initialize_with_owners(
+        env: Env,
+        owners: Vec<OwnerInit>,
+        required_approvals: u32,
+    ) -> Result<(), MultisigError> {
+        let mut addresses = Vec::new(&env);
+        let mut weights = Map::new(&env);
+        let mut roles = Map::new(&env);
+        for owner in owners.iter() {
+            if owner.weight == 0 {
+                return Err(MultisigError::InvalidThreshold);
+            }
+            if owner.label.len() > MAX_OWNER_LABEL_LEN {
+                return Err(MultisigError::InvalidMetadata);
+            }
+            if owner.weight != 1 {
+                weights.set(owner.address.clone(), owner.weight);
+            }
+            if owner.role != OwnerRole::Full {
+                roles.set(owner.address.clone(), owner.role);
+            }
+            addresses.push_back(owner.address);
+        }
+
+        Self::setup_wallet(&env, addresses, weights, roles, required_approvals)?;
+
+        for owner in owners.iter() {
+            if !owner.label.is_empty() {
+                env.storage().persistent().set(&(OWNER_LABEL_KEY, owner.address), &owner.label);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Proposes seating `owner` with one vote and every role.
+    pub fn propose_add_owner(env: Env, caller: Address, owner: Address) -> Result<u32, MultisigError> {
+        Self::submit_config_change(env, caller, ConfigChange::AddOwner(owner))
+    }
+
+    /// Proposes removing `owner`'s seat. Once executed, their approvals on
+    /// pending proposals no longer count.
+    pub fn propose_remove_owner(env: Env, caller: Address, owner: Address) -> Result<u32, MultisigError> {
+        Self::submit_config_change(env, caller, ConfigChange::RemoveOwner(owner))
+    }
+
+    /// Proposes handing `old`'s seat to `new`.
+    pub fn propose_replace_owner(env: Env, caller: Address, old: Address, new: Address) -> Result<u32, MultisigError> {
+        let change = ExtendedChange::ReplaceOwner(OwnerReplacement { old, new });
+        Self::submit_config_change(env, caller, ConfigChange::Extended(change))
+    }
+
+    /// Proposes giving `owner`'s seat `weight` votes.
+    pub fn propose_owner_weight(env: Env, caller: Address, owner: Address, weight: u32) -> Result<u32, MultisigError> {
+        Self::submit_config_change(env, caller, ConfigChange::Extended(ExtendedChange::SetOwnerWeight(owner, weight)))
+    }
+
+    /// Proposes restricting `owner`'s seat to `role`.
+    pub fn propose_owner_role(env: Env, caller: Address, owner: Address, role: OwnerRole) -> Result<u32, MultisigError> {
+        Self::submit_config_change(env, caller, ConfigChange::Extended(ExtendedChange::SetOwnerRole(owner, role)))
+    }
+
+    pub fn get_owner_weight(env: Env, owner: Address) -> Result<u32, MultisigError> {
+        let config = Self::get_config(&env)?;
+        if !config.owners.contains(&owner) {
+            return Err(MultisigError::InvalidOwner);
+        }
+        Ok(Self::owner_weight(&config, &owner))
+    }
+
+    pub fn get_owner_role(env: Env, owner: Address) -> Result<OwnerRole, MultisigError> {
+        let config = Self::get_config(&env)?;
+        if !config.owners.contains(&owner) {
+            return Err(MultisigError::InvalidOwner);
+        }
+        Ok(Self::owner_role(&config, &owner))
+    }
+
+    pub fn get_owner_label(env: Env, owner: Address) -> Option<String> {
+        env.storage().persistent().get(&(OWNER_LABEL_KEY, owner))
+    }
+}
@@ -0,0 +1,158 @@
+//! Passkey recovery. An owner may enroll a backup secp256r1 passkey. If their
+//! main key is lost, the passkey declares the loss and names a successor
+//! address; once the owner's chosen waiting period has passed without them
+//! cancelling, anyone can hand the seat over. Each declaration spends a
+//! nonce, so a cancelled claim cannot be revived with the same signature.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env, Symbol};
+
+use crate::{MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError};
+
+pub(crate) const PASSKEY_KEY: Symbol = symbol_short!("passkey");
+const KEY_LOSS_KEY: Symbol = symbol_short!("key_loss");
+pub(crate) const KEY_LOSS_NONCE_KEY: Symbol = symbol_short!("kl_nonce");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PasskeyEnrollment {
+    /// Uncompressed SEC-1 secp256r1 public key.
+    pub public_key: BytesN<65>,
+    /// Ledgers the other owners, and the owner, have to notice a declared loss.
+    pub waiting_period: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeyLossClaim {
+    pub successor: Address,
+    pub declared_at: u32,
+    /// First ledger at which the seat may be handed over.
+    pub executable_at: u32,
+}
+
+impl MultisigContract {
+    fn key_loss_message(env: &Env, owner: &Address, successor: &Address, nonce: u64) -> Bytes {
+        (env.current_contract_address(), symbol_short!("key_loss"), owner.clone(), successor.clone(), nonce)
+            .to_xdr(env)
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Enrolls `caller`'s backup passkey, replacing any earlier one and
+    /// voiding any loss it declared.
+    pub fn enroll_passkey(env: Env, caller: Address, enrollment: PasskeyEnrollment) -> Result<(), MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        if enrollment.waiting_period == 0 {
+            return Err(MultisigError::InvalidSchedule);
+        }
+
+        env.storage().persistent().set(&(PASSKEY_KEY, caller.clone()), &enrollment);
+        env.storage().persistent().remove(&(KEY_LOSS_KEY, caller.clone()));
+        Self::publish_event(&env, (symbol_short!("passkey"), caller), enrollment.waiting_period);
+
+        Ok(())
+    }
+
+    pub fn remove_passkey(env: Env, caller: Address) -> Result<(), MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        env.storage().persistent().remove(&(PASSKEY_KEY, caller.clone()));
+        env.storage().persistent().remove(&(KEY_LOSS_KEY, caller.clone()));
+        Self::publish_event(&env, (symbol_short!("passk_del"), caller), ());
+
+        Ok(())
+    }
+
+    /// Bytes the passkey signs, as a SHA-256 digest, to declare `owner`'s
+    /// key lost in favour of `successor`, spending nonce `nonce`.
+    pub fn get_key_loss_message(env: Env, owner: Address, successor: Address, nonce: u64) -> Bytes {
+        Self::key_loss_message(&env, &owner, &successor, nonce)
+    }
+
+    /// The nonce `owner`'s next key loss declaration must carry.
+    pub fn get_key_loss_nonce(env: Env, owner: Address) -> u64 {
+        env.storage().persistent().get(&(KEY_LOSS_NONCE_KEY, owner)).unwrap_or(0)
+    }
+
+    /// Declares `owner`'s main key lost, authorized by their enrolled
+    /// passkey over their next nonce. Starts the waiting period, which the
+    /// owner can end with `cancel_key_loss` if the claim is not theirs.
+    pub fn declare_key_loss(
+        env: Env,
+        owner: Address,
+        successor: Address,
+        nonce: u64,
+        signature: BytesN<64>,
+    ) -> Result<KeyLossClaim, MultisigError> {
+        Self::ensure_owner(&env, &owner)?;
+        let enrollment: PasskeyEnrollment = env.storage().persistent().get(&(PASSKEY_KEY, owner.clone()))
+            .ok_or(MultisigError::RecoveryNotConfigured)?;
+        if successor == owner || Self::is_unusable_address(&env, &successor) {
+            return Err(MultisigError::InvalidAddress);
+        }
+
+        let expected = Self::get_key_loss_nonce(env.clone(), owner.clone());
+        if nonce != expected {
+            return Err(MultisigError::Unauthorized);
+        }
+        let digest = env.crypto().sha256(&Self::key_loss_message(&env, &owner, &successor, nonce));
+        env.crypto().secp256r1_verify(&enrollment.public_key, &digest, &signature);
+        let next = expected.checked_add(1).ok_or(MultisigError::ArithmeticError)?;
+        env.storage().persistent().set(&(KEY_LOSS_NONCE_KEY, owner.clone()), &next);
+
+        let declared_at = env.ledger().sequence();
+        let claim = KeyLossClaim {
+            successor: successor.clone(),
+            declared_at,
+            executable_at: declared_at.checked_add(enrollment.waiting_period)
+                .ok_or(MultisigError::ArithmeticError)?,
+        };
+        env.storage().persistent().set(&(KEY_LOSS_KEY, owner.clone()), &claim);
+        Self::publish_event(&env, (symbol_short!("key_loss"), owner), (successor, claim.executable_at));
+
+        Ok(claim)
+    }
+
+    pub fn cancel_key_loss(env: Env, caller: Address) -> Result<(), MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        let key = (KEY_LOSS_KEY, caller.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(MultisigError::RecoveryNotRequested);
+        }
+        env.storage().persistent().remove(&key);
+        Self::publish_event(&env, (symbol_short!("loss_void"), caller), ());
+
+        Ok(())
+    }
+
+    /// Permissionless: hands the seat to the declared successor once the
+    /// waiting period is over.
+    pub fn complete_key_loss(env: Env, owner: Address) -> Result<(), MultisigError> {
+        let claim: KeyLossClaim = env.storage().persistent().get(&(KEY_LOSS_KEY, owner.clone()))
+            .ok_or(MultisigError::RecoveryNotRequested)?;
+        if env.ledger().sequence() < claim.executable_at {
+            return Err(MultisigError::OwnerStillActive);
+        }
+
+        Self::replace_seat(&env, &owner, &claim.successor)?;
+        env.storage().persistent().remove(&(PASSKEY_KEY, owner.clone()));
+        env.storage().persistent().remove(&(KEY_LOSS_KEY, owner.clone()));
+        Self::publish_event(&env, (symbol_short!("passk_rec"), owner), claim.successor);
+
+        Ok(())
+    }
+
+    pub fn get_passkey(env: Env, owner: Address) -> Option<PasskeyEnrollment> {
+        env.storage().persistent().get(&(PASSKEY_KEY, owner))
+    }
+
+    pub fn get_key_loss_claim(env: Env, owner: Address) -> Option<KeyLossClaim> {
+        env.storage().persistent().get(&(KEY_LOSS_KEY, owner))
+    }
+}
@@ -0,0 +1,81 @@
+//! Circuit breaker for a suspected key compromise. Stronger than a lockdown:
+//! a designated pauser freezes submitting, approving and executing
+//! proposals as well as every other execution path. Only an `Unpause`
+//! proposal still goes through, and it needs the full threshold. While
+//! paused the wallet reports `Paused`.
+
+use soroban_sdk::{contractimpl, symbol_short, Address, Env, Error, Symbol, Vec};
+
+use crate::{
+    ConfigChange, ExtendedChange, ExtendedError, MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError,
+    TransactionKind,
+};
+
+pub(crate) const PAUSED_KEY: Symbol = symbol_short!("paused");
+pub(crate) const PAUSERS_KEY: Symbol = symbol_short!("pausers");
+
+impl MultisigContract {
+    /// Whether `kind` lifts a pause, and so is exempt from it.
+    pub(crate) fn lifts_pause(kind: &TransactionKind) -> bool {
+        matches!(kind, TransactionKind::Config(ConfigChange::Extended(ExtendedChange::Unpause)))
+    }
+
+    /// Rejects submitting or approving anything but an `Unpause` proposal
+    /// while the wallet is paused.
+    pub(crate) fn ensure_not_paused(env: &Env, kind: &TransactionKind) -> Result<(), Error> {
+        if Self::is_paused(env.clone()) && !Self::lifts_pause(kind) {
+            return Err(ExtendedError::Paused.into());
+        }
+
+        Ok(())
+    }
+
+    /// Names the owners allowed to pause; an empty list lets every owner.
+    pub(crate) fn set_pausers(env: &Env, pausers: Vec<Address>) -> Result<(), MultisigError> {
+        let config = Self::get_config(env)?;
+        if pausers.iter().any(|pauser| !config.owners.contains(&pauser)) {
+            return Err(MultisigError::InvalidOwner);
+        }
+        if pausers.is_empty() {
+            env.storage().persistent().remove(&PAUSERS_KEY);
+        } else {
+            env.storage().persistent().set(&PAUSERS_KEY, &pausers);
+        }
+        Self::publish_event(env, (symbol_short!("pausers"),), pausers);
+
+        Ok(())
+    }
+
+    pub(crate) fn unpause(env: &Env) {
+        env.storage().persistent().remove(&PAUSED_KEY);
+        Self::publish_event(env, (symbol_short!("unpaused"),), ());
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Freezes the wallet at once. Undoing it takes an executed `Unpause`
+    /// proposal.
+    pub fn pause(env: Env, caller: Address) -> Result<(), MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+        let pausers = Self::get_pausers(env.clone());
+        if !pausers.is_empty() && !pausers.contains(&caller) {
+            return Err(MultisigError::Unauthorized);
+        }
+
+        env.storage().persistent().set(&PAUSED_KEY, &env.ledger().sequence());
+        Self::publish_event(&env, (symbol_short!("paused"),), caller);
+
+        Ok(())
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        env.storage().persistent().has(&PAUSED_KEY)
+    }
+
+    /// Owners allowed to pause; empty when every owner may.
+    pub fn get_pausers(env: Env) -> Vec<Address> {
+        env.storage().persistent().get(&PAUSERS_KEY).unwrap_or_else(|| Vec::new(&env))
+    }
+}
@@ -0,0 +1,129 @@
+//! Payment requests from outside the wallet. The quorum may admit external
+//! addresses, such as vendors, as requesters. A requester files a request for
+//! payment; it only becomes a pending proposal once an owner sponsors it, so
+//! requesters can fill the intake queue but never spend.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, BytesN, Env, Symbol};
+
+use crate::{MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError, TransactionKind};
+
+const REQUESTER_KEY: Symbol = symbol_short!("requester");
+const PAYMENT_REQUEST_KEY: Symbol = symbol_short!("pay_req");
+pub(crate) const PAYMENT_REQUEST_COUNT_KEY: Symbol = symbol_short!("pay_cnt");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentRequest {
+    pub requester: Address,
+    pub to: Address,
+    pub amount: i128,
+    pub token: Address,
+    pub data: BytesN<32>,
+    pub filed_at: u32,
+}
+
+impl MultisigContract {
+    pub(crate) fn set_requester(env: &Env, requester: Address, allowed: bool) -> Result<(), MultisigError> {
+        if Self::is_unusable_address(env, &requester) {
+            return Err(MultisigError::InvalidAddress);
+        }
+        if allowed {
+            env.storage().persistent().set(&(REQUESTER_KEY, requester.clone()), &true);
+        } else {
+            env.storage().persistent().remove(&(REQUESTER_KEY, requester.clone()));
+        }
+        Self::publish_event(env, (symbol_short!("requester"), requester), allowed);
+
+        Ok(())
+    }
+
+    fn payment_request(env: &Env, request_id: u32) -> Result<PaymentRequest, MultisigError> {
+        env.storage().persistent().get(&(PAYMENT_REQUEST_KEY, request_id))
+            .ok_or(MultisigError::TransactionNotFound)
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Files a request for the wallet to pay `amount` of `token` to `to`.
+    /// Only admitted requesters may file.
+    pub fn file_payment_request(
+        env: Env,
+        requester: Address,
+        to: Address,
+        amount: i128,
+        token: Address,
+        data: BytesN<32>,
+    ) -> Result<u32, MultisigError> {
+        requester.require_auth();
+        if !Self::is_requester(env.clone(), requester.clone()) {
+            return Err(MultisigError::Unauthorized);
+        }
+        Self::check_transfer_inputs(&env, &to, amount, &token, false)?;
+
+        let count: u32 = env.storage().persistent().get(&PAYMENT_REQUEST_COUNT_KEY).unwrap_or(0);
+        let request_id = count.checked_add(1).ok_or(MultisigError::ArithmeticError)?;
+        env.storage().persistent().set(&PAYMENT_REQUEST_COUNT_KEY, &request_id);
+
+        let request = PaymentRequest {
+            requester: requester.clone(),
+            to: to.clone(),
+            amount,
+            token: token.clone(),
+            data,
+            filed_at: env.ledger().sequence(),
+        };
+        env.storage().persistent().set(&(PAYMENT_REQUEST_KEY, request_id), &request);
+
+        Self::publish_event(&env, (symbol_short!("pay_req"), request_id), (requester, to, amount, token));
+
+        Ok(request_id)
+    }
+
+    /// Turns a request into a pending transfer proposal submitted by
+    /// `caller`, returning the proposal id.
+    pub fn sponsor_payment_request(env: Env, caller: Address, request_id: u32) -> Result<u32, MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+        let request = Self::payment_request(&env, request_id)?;
+        Self::validate_transaction_inputs(&env, &request.to, request.amount, &request.token)?;
+
+        let transaction_id = Self::store_transaction(
+            &env,
+            &caller,
+            request.to,
+            request.amount,
+            request.token,
+            request.data,
+            TransactionKind::Transfer,
+        )?;
+        env.storage().persistent().remove(&(PAYMENT_REQUEST_KEY, request_id));
+
+        Self::publish_event(&env, (symbol_short!("pay_spon"), request_id), (caller, transaction_id));
+
+        Ok(transaction_id)
+    }
+
+    /// Drops a request, either by an owner turning it down or by the
+    /// requester withdrawing it.
+    pub fn decline_payment_request(env: Env, caller: Address, request_id: u32) -> Result<(), MultisigError> {
+        caller.require_auth();
+        let request = Self::payment_request(&env, request_id)?;
+        if caller != request.requester {
+            Self::verify_owner(&env, &caller)?;
+        }
+        env.storage().persistent().remove(&(PAYMENT_REQUEST_KEY, request_id));
+
+        Self::publish_event(&env, (symbol_short!("pay_decl"), request_id), caller);
+
+        Ok(())
+    }
+
+    pub fn get_payment_request(env: Env, request_id: u32) -> Option<PaymentRequest> {
+        env.storage().persistent().get(&(PAYMENT_REQUEST_KEY, request_id))
+    }
+
+    pub fn is_requester(env: Env, address: Address) -> bool {
+        env.storage().persistent().has(&(REQUESTER_KEY, address))
+    }
+}
@@ -0,0 +1,95 @@
+//! Wallet periods. Features with per-period limits, such as the fast lane's
+//! daily total or the cap on urgent submissions, count against the same
+//! calendar: periods of a configured length, with boundaries shifted by an
+//! offset so a "day" can follow the business day rather than midnight UTC.
+//! Changing the calendar keeps the numbering going from the period the
+//! change falls in, so usage already recorded there still counts until the
+//! new calendar's next boundary.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Env, Symbol};
+
+use crate::{MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError};
+
+pub(crate) const CALENDAR_KEY: Symbol = symbol_short!("calendar");
+/// When the calendar last changed, and the period index in effect then.
+pub(crate) const PERIOD_ANCHOR_KEY: Symbol = symbol_short!("per_anchr");
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PeriodCalendar {
+    /// Length of a period in seconds.
+    pub length: u64,
+    /// Seconds after each multiple of `length` since the Unix epoch at which
+    /// a period starts, e.g. 50,400 for days starting 09:00 at UTC-5.
+    pub offset: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PeriodWindow {
+    pub index: u64,
+    pub starts_at: u64,
+    /// First second of the next period.
+    pub ends_at: u64,
+}
+
+impl MultisigContract {
+    pub(crate) fn set_period_calendar(env: &Env, calendar: PeriodCalendar) -> Result<(), MultisigError> {
+        if calendar.length == 0 || calendar.offset >= calendar.length {
+            return Err(MultisigError::InvalidSchedule);
+        }
+        let now = env.ledger().timestamp();
+        let index = Self::current_period(env);
+        env.storage().persistent().set(&CALENDAR_KEY, &calendar);
+        env.storage().persistent().set(&PERIOD_ANCHOR_KEY, &(now, index));
+        Self::publish_event(env, (symbol_short!("calendar"),), (calendar.length, calendar.offset));
+
+        Ok(())
+    }
+
+    /// Index of the period containing `timestamp`. Timestamps before the
+    /// first offset boundary fall in period zero.
+    pub(crate) fn period_at(calendar: &PeriodCalendar, timestamp: u64) -> u64 {
+        timestamp.saturating_sub(calendar.offset) / calendar.length
+    }
+
+    /// Numbers the period containing `timestamp`, counting on from the
+    /// period the last calendar change fell in.
+    fn period_index(env: &Env, calendar: &PeriodCalendar, timestamp: u64) -> u64 {
+        let period = Self::period_at(calendar, timestamp);
+        match env.storage().persistent().get::<_, (u64, u64)>(&PERIOD_ANCHOR_KEY) {
+            Some((changed_at, index)) => {
+                index.saturating_add(period.saturating_sub(Self::period_at(calendar, changed_at)))
+            },
+            None => period,
+        }
+    }
+
+    /// Index of the period the current ledger falls in.
+    pub(crate) fn current_period(env: &Env) -> u64 {
+        Self::period_index(env, &Self::get_period_calendar(env.clone()), env.ledger().timestamp())
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// The wallet's calendar; UTC days unless configured otherwise.
+    pub fn get_period_calendar(env: Env) -> PeriodCalendar {
+        env.storage().persistent().get(&CALENDAR_KEY)
+            .unwrap_or(PeriodCalendar { length: SECONDS_PER_DAY, offset: 0 })
+    }
+
+    pub fn get_current_period(env: Env) -> Result<PeriodWindow, MultisigError> {
+        let calendar = Self::get_period_calendar(env.clone());
+        let period = Self::period_at(&calendar, env.ledger().timestamp());
+        let index = Self::period_index(&env, &calendar, env.ledger().timestamp());
+        let starts_at = period.checked_mul(calendar.length)
+            .and_then(|start| start.checked_add(calendar.offset))
+            .ok_or(MultisigError::ArithmeticError)?;
+        let ends_at = starts_at.checked_add(calendar.length).ok_or(MultisigError::ArithmeticError)?;
+
+        Ok(PeriodWindow { index, starts_at, ends_at })
+    }
+}
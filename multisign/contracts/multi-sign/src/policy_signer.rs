@@ -0,0 +1,84 @@
+//! Policy co-signer. The quorum may name one contract, such as a risk engine,
+//! whose approval of a proposal counts as one more approval. It holds no owner
+//! seat, so thresholds stay reachable by the human owners alone, and replacing
+//! or removing it voids whatever it approved so far.
+
+use soroban_sdk::{contractclient, contractimpl, symbol_short, Address, Env, Symbol};
+
+use crate::{MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError, Transaction, TX_KEY};
+
+pub(crate) const POLICY_SIGNER_KEY: Symbol = symbol_short!("pol_sign");
+
+/// Weight of the policy co-signer's approval.
+const POLICY_SIGNER_WEIGHT: u32 = 1;
+
+#[contractclient(name = "PolicyCosignerClient")]
+pub trait PolicyCosigner {
+    /// Whether the policy approves `transaction`.
+    fn check(env: Env, wallet: Address, transaction_id: u32, transaction: Transaction) -> bool;
+}
+
+impl MultisigContract {
+    pub(crate) fn set_policy_signer(env: &Env, policy: Option<Address>) -> Result<(), MultisigError> {
+        match &policy {
+            Some(policy) if Self::is_unusable_address(env, policy) => return Err(MultisigError::InvalidAddress),
+            Some(policy) => env.storage().persistent().set(&POLICY_SIGNER_KEY, policy),
+            None => env.storage().persistent().remove(&POLICY_SIGNER_KEY),
+        }
+        Self::publish_event(env, (symbol_short!("pol_sign"),), policy);
+
+        Ok(())
+    }
+
+    /// The proposal's approvals including the current policy co-signer's, if
+    /// it approved this very content.
+    pub(crate) fn effective_approvals(env: &Env, transaction_id: u32, transaction: &Transaction) -> u32 {
+        let approvals = Self::owner_approvals(env, transaction_id, transaction);
+        let Some(policy) = Self::get_policy_signer(env.clone()) else {
+            return approvals;
+        };
+        match Self::approval_record(env, transaction_id, &policy) {
+            Some(record) if record.content_hash == Self::proposal_hash(env, transaction) => {
+                approvals.saturating_add(POLICY_SIGNER_WEIGHT)
+            },
+            _ => approvals,
+        }
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    pub fn get_policy_signer(env: Env) -> Option<Address> {
+        env.storage().persistent().get(&POLICY_SIGNER_KEY)
+    }
+
+    /// Permissionless: asks the policy co-signer to approve a pending
+    /// proposal, recording its approval if it does.
+    pub fn request_policy_approval(env: Env, transaction_id: u32) -> Result<(), MultisigError> {
+        let policy = Self::get_policy_signer(env.clone()).ok_or(MultisigError::Unauthorized)?;
+        let transaction: Transaction = env.storage().persistent().get(&(TX_KEY, transaction_id))
+            .ok_or(MultisigError::TransactionNotFound)?;
+        Self::ensure_pending(&env, &transaction)?;
+        if Self::has_approved(&env, transaction_id, &policy) {
+            return Err(MultisigError::AlreadyApproved);
+        }
+
+        let approved = PolicyCosignerClient::new(&env, &policy)
+            .check(&env.current_contract_address(), &transaction_id, &transaction);
+        if !approved {
+            return Err(MultisigError::Unauthorized);
+        }
+
+        Self::store_approval(&env, transaction_id, &policy, &transaction);
+        let approvals = Self::effective_approvals(&env, transaction_id, &transaction);
+        Self::publish_event(&env, (symbol_short!("pol_appr"), transaction_id), (policy, approvals));
+
+        let config = Self::get_config(&env)?;
+        let required = Self::required_approvals_for(&env, &config, &transaction);
+        if Self::owner_approvals(&env, transaction_id, &transaction) < required && approvals >= required {
+            Self::publish_quorum_reached(&env, &config, transaction_id, &transaction, approvals);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+//! Pre-authorized external auth entries. The quorum approves the signature
+//! payload hash of one Soroban authorization entry ahead of time, so the wallet
+//! can co-sign a transaction that a partner account builds and submits.
+
+use soroban_sdk::{contractimpl, symbol_short, BytesN, Env, Symbol};
+
+use crate::{MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError};
+
+const PREAUTH_KEY: Symbol = symbol_short!("preauth");
+
+impl MultisigContract {
+    pub(crate) fn store_preauthorization(
+        env: &Env,
+        payload: BytesN<32>,
+        expires_at: u32,
+    ) -> Result<(), MultisigError> {
+        if expires_at < env.ledger().sequence() {
+            return Err(MultisigError::InvalidSchedule);
+        }
+
+        env.storage().persistent().set(&(PREAUTH_KEY, payload.clone()), &expires_at);
+        Self::publish_event(env, (symbol_short!("preauth"), payload), expires_at);
+
+        Ok(())
+    }
+
+    pub(crate) fn revoke_preauthorization(env: &Env, payload: BytesN<32>) {
+        env.storage().persistent().remove(&(PREAUTH_KEY, payload.clone()));
+        Self::publish_event(env, (symbol_short!("preauth_x"), payload), ());
+    }
+
+    /// Accepts `payload` once if the quorum pre-authorized it and it has not expired.
+    pub(crate) fn consume_preauthorization(env: &Env, payload: &BytesN<32>) -> Result<(), MultisigError> {
+        let key = (PREAUTH_KEY, payload.clone());
+        let expires_at: u32 = env.storage().persistent().get(&key)
+            .ok_or(MultisigError::Unauthorized)?;
+        if env.ledger().sequence() > expires_at {
+            return Err(MultisigError::Unauthorized);
+        }
+
+        env.storage().persistent().remove(&key);
+
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Whether `payload` is pre-authorized and still within its expiry.
+    pub fn is_preauthorized(env: Env, payload: BytesN<32>) -> bool {
+        env.storage().persistent().get::<_, u32>(&(PREAUTH_KEY, payload))
+            .is_some_and(|expires_at| env.ledger().sequence() <= expires_at)
+    }
+}
@@ -0,0 +1,140 @@
+//! Dry runs of configuration changes, reporting how they would reshape the
+//! signer set before anyone approves them.
+
+use soroban_sdk::{contractimpl, contracttype, Address, Env, Vec};
+
+use crate::{
+    ConfigChange, ExtendedChange, MultisigConfig, MultisigContract, MultisigContractArgs, MultisigContractClient,
+    MultisigError, REJECTION_KEY,
+};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigImpact {
+    pub owner_count: u32,
+    pub required_approvals: u32,
+    pub cancel_threshold: u32,
+    pub removed_owners: Vec<Address>,
+    /// Pending proposals holding a vote from a removed owner, which would no
+    /// longer be attributable to a current signer.
+    pub affected_pending: Vec<u32>,
+    /// Whether the owners active afterwards could still reach the threshold.
+    pub quorum_reachable: bool,
+}
+
+impl MultisigContract {
+    /// Applies the structural part of `change` to `config`, returning the
+    /// owner who would join the wallet, if any.
+    fn preview_structure(
+        env: &Env,
+        config: &mut MultisigConfig,
+        change: &ConfigChange,
+    ) -> Result<Option<Address>, MultisigError> {
+        match change {
+            ConfigChange::AddOwner(owner) => {
+                Self::seat_owner(env, config, owner)?;
+                Ok(Some(owner.clone()))
+            },
+            ConfigChange::RemoveOwner(owner) => {
+                Self::unseat_owner(config, owner)?;
+                Ok(None)
+            },
+            ConfigChange::AcknowledgeRecovery(owner) => {
+                let recovery = Self::get_recovery_contact(env.clone(), owner.clone())
+                    .ok_or(MultisigError::RecoveryNotConfigured)?;
+                if config.owners.contains(&recovery.contact) {
+                    return Err(MultisigError::DuplicateOwner);
+                }
+                let index = config.owners.first_index_of(owner).ok_or(MultisigError::InvalidOwner)?;
+                config.owners.set(index, recovery.contact.clone());
+                Self::transfer_seat(config, owner, &recovery.contact);
+                Ok(Some(recovery.contact))
+            },
+            ConfigChange::SetThreshold(new_threshold) => {
+                Self::validate_threshold(config, *new_threshold)?;
+                config.required_approvals = *new_threshold;
+                Ok(None)
+            },
+            ConfigChange::SetCancelThreshold(new_threshold) => {
+                Self::validate_threshold(config, *new_threshold)?;
+                config.cancel_threshold = *new_threshold;
+                Ok(None)
+            },
+            ConfigChange::RecoverQuorum(new_threshold) => {
+                config.required_approvals = *new_threshold;
+                config.cancel_threshold = config.cancel_threshold.min(*new_threshold);
+                Ok(None)
+            },
+            ConfigChange::SetInactivityPeriod(period) => {
+                config.inactivity_period = *period;
+                Ok(None)
+            },
+            ConfigChange::Extended(ExtendedChange::ReplaceOwner(replacement)) => {
+                if config.owners.contains(&replacement.new) {
+                    return Err(MultisigError::DuplicateOwner);
+                }
+                let index = config.owners.first_index_of(&replacement.old).ok_or(MultisigError::InvalidOwner)?;
+                config.owners.set(index, replacement.new.clone());
+                Self::transfer_seat(config, &replacement.old, &replacement.new);
+                Ok(Some(replacement.new.clone()))
+            },
+            _ => Ok(None),
+        }
+    }
+
+    fn has_vote_from(env: &Env, transaction_id: u32, owners: &Vec<Address>) -> bool {
+        let rejections: Vec<Address> = env.storage().persistent().get(&(REJECTION_KEY, transaction_id))
+            .unwrap_or_else(|| Vec::new(env));
+        owners.iter().any(|owner| {
+            Self::has_approved(env, transaction_id, &owner) || rejections.contains(&owner)
+        })
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Reports what executing `change` would do to the owner set, threshold
+    /// and pending proposals, without changing anything. Fails with the error
+    /// execution would raise if the change cannot apply to the current owners.
+    pub fn preview_config_change(env: Env, change: ConfigChange) -> Result<ConfigImpact, MultisigError> {
+        let current = Self::get_config(&env)?;
+        let mut config = current.clone();
+        let joining = Self::preview_structure(&env, &mut config, &change)?;
+
+        let mut removed_owners = Vec::new(&env);
+        for owner in current.owners.iter() {
+            if !config.owners.contains(&owner) {
+                removed_owners.push_back(owner);
+            }
+        }
+
+        let mut affected_pending = Vec::new(&env);
+        if !removed_owners.is_empty() {
+            for id in Self::get_pending_transactions(env.clone()).iter() {
+                if Self::has_vote_from(&env, id, &removed_owners) {
+                    affected_pending.push_back(id);
+                }
+            }
+        }
+
+        // Owners joining start out active; their activity is recorded on entry.
+        let mut active = Self::active_owner_count(&env, &config);
+        if let Some(owner) = joining {
+            if config.inactivity_period > 0
+                && Self::owner_role(&config, &owner).can_vote()
+                && !Self::is_active(&env, &config, &owner)
+            {
+                active = active.saturating_add(Self::owner_weight(&config, &owner));
+            }
+        }
+
+        Ok(ConfigImpact {
+            owner_count: config.owners.len(),
+            required_approvals: config.required_approvals,
+            cancel_threshold: config.cancel_threshold,
+            removed_owners,
+            affected_pending,
+            quorum_reachable: active >= config.required_approvals,
+        })
+    }
+}
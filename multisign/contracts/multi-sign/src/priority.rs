@@ -0,0 +1,152 @@
+//! Urgent transfers. An owner may flag a transfer as urgent, which puts it at
+//! the front of the pending queue and lets it execute with the expedited
+//! threshold the quorum configured. Each owner may only file so many urgent
+//! transfers per period, so the flag cannot become the normal way to pay.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, BytesN, Env, Symbol, Vec};
+
+use crate::{
+    MultisigConfig, MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError,
+    OwnerRole, Transaction, TransactionKind, TransactionStatus, TX_KEY,
+};
+
+pub(crate) const URGENT_POLICY_KEY: Symbol = symbol_short!("urgent");
+const URGENT_USAGE_KEY: Symbol = symbol_short!("urg_use");
+
+const MAX_QUEUE_LIMIT: u32 = 50;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Priority {
+    Normal,
+    Urgent,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UrgentPolicy {
+    /// Approvals an urgent transfer needs, if lower than it would otherwise.
+    pub threshold: u32,
+    /// Urgent transfers each owner may submit per wallet period.
+    pub max_per_period: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum UrgentHandling {
+    Disabled,
+    Enabled(UrgentPolicy),
+}
+
+impl MultisigContract {
+    pub(crate) fn set_urgent_handling(
+        env: &Env,
+        config: &MultisigConfig,
+        handling: UrgentHandling,
+    ) -> Result<(), MultisigError> {
+        match &handling {
+            UrgentHandling::Enabled(policy)
+                if policy.threshold == 0 || policy.threshold > Self::voting_weight(config) =>
+            {
+                return Err(MultisigError::InvalidThreshold);
+            },
+            UrgentHandling::Enabled(policy) => env.storage().persistent().set(&URGENT_POLICY_KEY, policy),
+            UrgentHandling::Disabled => env.storage().persistent().remove(&URGENT_POLICY_KEY),
+        }
+        Self::publish_event(env, (symbol_short!("urgent"),), handling);
+
+        Ok(())
+    }
+
+    /// `required` lowered to the expedited threshold for an urgent transfer.
+    pub(crate) fn expedited_approvals(env: &Env, transaction: &Transaction, required: u32) -> u32 {
+        if transaction.priority != Priority::Urgent {
+            return required;
+        }
+
+        Self::get_urgent_policy(env.clone()).map_or(required, |policy| policy.threshold.min(required))
+    }
+
+    /// Counts one urgent submission by `caller` against this period's cap.
+    fn record_urgent_submission(env: &Env, caller: &Address, policy: &UrgentPolicy) -> Result<(), MultisigError> {
+        let key = (URGENT_USAGE_KEY, caller.clone(), Self::current_period(env));
+        let used: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+        if used >= policy.max_per_period {
+            return Err(MultisigError::AmountExceedsLimit);
+        }
+        env.storage().persistent().set(&key, &(used + 1));
+
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Submits a transfer flagged urgent. Fails with `AmountExceedsLimit` once
+    /// the caller has used up this period's urgent submissions.
+    pub fn submit_urgent_transaction(
+        env: Env,
+        caller: Address,
+        to: Address,
+        amount: i128,
+        token: Address,
+        data: BytesN<32>,
+    ) -> Result<u32, MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+        let config = Self::get_config(&env)?;
+        Self::ensure_role(&config, &caller, OwnerRole::can_propose)?;
+        Self::validate_transaction_inputs(&env, &to, amount, &token)?;
+
+        let policy = Self::get_urgent_policy(env.clone()).ok_or(MultisigError::Unauthorized)?;
+        Self::record_urgent_submission(&env, &caller, &policy)?;
+
+        let id = Self::next_transaction_id(&env)?;
+        let mut transaction = Self::new_transaction(
+            &caller,
+            to,
+            amount,
+            token,
+            data,
+            TransactionKind::Transfer,
+            TransactionStatus::Pending,
+        );
+        transaction.priority = Priority::Urgent;
+        Self::open_transaction(&env, &config, id, transaction)?;
+        Self::publish_event(&env, (symbol_short!("urgent"), id), caller);
+
+        Ok(id)
+    }
+
+    pub fn get_urgent_policy(env: Env) -> Option<UrgentPolicy> {
+        env.storage().persistent().get(&URGENT_POLICY_KEY)
+    }
+
+    /// Up to `limit` (at most 50) open proposal ids, urgent ones first, each
+    /// group in submission order.
+    pub fn get_pending_queue(env: Env, limit: u32) -> Vec<u32> {
+        let limit = limit.min(MAX_QUEUE_LIMIT);
+        let mut urgent = Vec::new(&env);
+        let mut normal = Vec::new(&env);
+        for id in Self::get_pending_transactions(env.clone()).iter() {
+            let Some(transaction) = env.storage().persistent().get::<_, Transaction>(&(TX_KEY, id)) else {
+                continue;
+            };
+            if transaction.status != TransactionStatus::Pending || Self::is_overdue(&env, &transaction) {
+                continue;
+            }
+            match transaction.priority {
+                Priority::Urgent => urgent.push_back(id),
+                Priority::Normal if normal.len() < limit => normal.push_back(id),
+                Priority::Normal => {},
+            }
+        }
+
+        urgent.append(&normal);
+        while urgent.len() > limit {
+            urgent.pop_back();
+        }
+
+        urgent
+    }
+}
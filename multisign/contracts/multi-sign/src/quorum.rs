@@ -0,0 +1,95 @@
+//! Quorum reachability. With an inactivity period configured, owners who have
+//! not acted within it no longer count towards reaching the threshold. When the
+//! remaining active owners cannot reach it, an alarm is raised. While it
+//! stands, a `RecoverQuorum` proposal needs only the approval of every active
+//! owner.
+
+use soroban_sdk::{contractimpl, symbol_short, Address, Env, Symbol};
+
+use crate::{
+    MultisigConfig, MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError,
+    CONFIG_KEY,
+};
+
+pub(crate) const QUORUM_ALARM_KEY: Symbol = symbol_short!("alarm");
+
+impl MultisigContract {
+    /// Voting weight of the owners who acted within the inactivity period; of
+    /// all voting owners if none is set.
+    pub(crate) fn active_owner_count(env: &Env, config: &MultisigConfig) -> u32 {
+        if config.inactivity_period == 0 {
+            return Self::voting_weight(config);
+        }
+
+        let mut active = 0u32;
+        for owner in config.owners.iter() {
+            if Self::owner_role(config, &owner).can_vote() && Self::is_active(env, config, &owner) {
+                active = active.saturating_add(Self::owner_weight(config, &owner));
+            }
+        }
+        active
+    }
+
+    pub(crate) fn is_active(env: &Env, config: &MultisigConfig, owner: &Address) -> bool {
+        let last_active = Self::get_last_activity(env.clone(), owner.clone());
+        config.inactivity_period == 0
+            || last_active.saturating_add(config.inactivity_period) > env.ledger().sequence()
+    }
+
+    pub(crate) fn quorum_reachable(env: &Env, config: &MultisigConfig) -> bool {
+        Self::active_owner_count(env, config) >= config.required_approvals
+    }
+
+    /// Re-evaluates reachability, raising or clearing the alarm on a change.
+    pub(crate) fn refresh_quorum_alarm(env: &Env) -> Result<bool, MultisigError> {
+        let config = Self::get_config(env)?;
+        let active = Self::active_owner_count(env, &config);
+        let reachable = active >= config.required_approvals;
+        let alarmed = Self::is_quorum_alarm_raised(env.clone());
+
+        if !reachable && !alarmed {
+            env.storage().persistent().set(&QUORUM_ALARM_KEY, &env.ledger().sequence());
+            Self::publish_event(env, (symbol_short!("alarm"),), (active, config.required_approvals));
+        } else if reachable && alarmed {
+            env.storage().persistent().remove(&QUORUM_ALARM_KEY);
+            Self::publish_event(env, (symbol_short!("alarm_off"),), (active, config.required_approvals));
+        }
+
+        Ok(reachable)
+    }
+
+    /// Lowers the threshold to what the active owners can reach.
+    pub(crate) fn recover_quorum(env: &Env, new_threshold: u32) -> Result<(), MultisigError> {
+        if !Self::is_quorum_alarm_raised(env.clone()) {
+            return Err(MultisigError::Unauthorized);
+        }
+
+        let mut config = Self::get_config(env)?;
+        if new_threshold == 0 || new_threshold > Self::active_owner_count(env, &config) {
+            return Err(MultisigError::InvalidThreshold);
+        }
+
+        config.required_approvals = new_threshold;
+        config.cancel_threshold = config.cancel_threshold.min(new_threshold);
+        Self::bump_epoch(env, &mut config)?;
+        env.storage().persistent().set(&CONFIG_KEY, &config);
+
+        Self::publish_event(env, (symbol_short!("threshold"),), new_threshold);
+        Self::refresh_quorum_alarm(env)?;
+
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Permissionless: checks whether active owners can still reach the
+    /// threshold, raising or clearing the alarm. Returns reachability.
+    pub fn check_quorum(env: Env) -> Result<bool, MultisigError> {
+        Self::refresh_quorum_alarm(&env)
+    }
+
+    pub fn is_quorum_alarm_raised(env: Env) -> bool {
+        env.storage().persistent().has(&QUORUM_ALARM_KEY)
+    }
+}
@@ -0,0 +1,76 @@
+//! Settlement receipts. Every executed proposal leaves an immutable record of
+//! when and by whom it was executed and how the wallet's balance of its token
+//! moved.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, token, Address, Env, Symbol};
+
+use crate::{MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError, Transaction};
+
+pub(crate) const RECEIPT_KEY: Symbol = symbol_short!("receipt");
+pub(crate) const RECEIPT_SEQ_KEY: Symbol = symbol_short!("rcpt_seq");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Receipt {
+    pub transaction_id: u32,
+    /// Position among all receipts issued by this wallet, starting at 1.
+    pub sequence: u32,
+    pub executed_at: u32,
+    pub executor: Address,
+    pub token: Address,
+    /// Change in the wallet's balance of `token`, or `None` when `token`
+    /// could not be read as a token.
+    pub balance_delta: Option<i128>,
+}
+
+impl MultisigContract {
+    /// The wallet's balance of the proposal's token, if it is a token at all.
+    pub(crate) fn receipt_balance(env: &Env, transaction: &Transaction) -> Option<i128> {
+        let this = env.current_contract_address();
+        if transaction.token == this {
+            return None;
+        }
+        match token::Client::new(env, &transaction.token).try_balance(&this) {
+            Ok(Ok(balance)) => Some(balance),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn issue_receipt(
+        env: &Env,
+        executor: &Address,
+        transaction_id: u32,
+        transaction: &Transaction,
+        balance_before: Option<i128>,
+    ) -> Result<(), MultisigError> {
+        let balance_delta = match (balance_before, Self::receipt_balance(env, transaction)) {
+            (Some(before), Some(after)) => Some(after.checked_sub(before).ok_or(MultisigError::ArithmeticError)?),
+            _ => None,
+        };
+
+        let sequence: u32 = env.storage().persistent().get(&RECEIPT_SEQ_KEY).unwrap_or(0);
+        let sequence = sequence.checked_add(1).ok_or(MultisigError::ArithmeticError)?;
+        env.storage().persistent().set(&RECEIPT_SEQ_KEY, &sequence);
+
+        let receipt = Receipt {
+            transaction_id,
+            sequence,
+            executed_at: env.ledger().sequence(),
+            executor: executor.clone(),
+            token: transaction.token.clone(),
+            balance_delta,
+        };
+        env.storage().persistent().set(&(RECEIPT_KEY, transaction_id), &receipt);
+
+        Self::publish_event(env, (symbol_short!("receipt"), transaction_id), sequence);
+
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    pub fn get_receipt(env: Env, transaction_id: u32) -> Option<Receipt> {
+        env.storage().persistent().get(&(RECEIPT_KEY, transaction_id))
+    }
+}
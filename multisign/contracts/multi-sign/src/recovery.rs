@@ -0,0 +1,214 @@
+//! Per-owner recovery. Each owner may name a recovery contact. Once that owner
+//! has been inactive for their chosen timeout, the contact can request the
+//! seat, and a quorum-approved `AcknowledgeRecovery` proposal hands it over.
+//! Contacts and the quorum can rehearse the same flow at any time: a
+//! rehearsal runs every step against a copy of the signer set and records
+//! how it went, leaving the real seats untouched.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, Env, Symbol};
+
+use crate::{
+    ConfigChange, MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError, CONFIG_KEY,
+};
+
+const RECOVERY_KEY: Symbol = symbol_short!("recovery");
+const RECOVERY_REQUEST_KEY: Symbol = symbol_short!("recov_req");
+pub(crate) const ACTIVITY_KEY: Symbol = symbol_short!("activity");
+const REHEARSAL_REQUEST_KEY: Symbol = symbol_short!("rehrs_req");
+const REHEARSAL_KEY: Symbol = symbol_short!("rehearsal");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryContact {
+    pub contact: Address,
+    /// Ledgers without activity from the owner before the contact may step in.
+    pub inactivity_timeout: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryRehearsal {
+    /// Contact who ran the rehearsal.
+    pub contact: Address,
+    pub requested_at: u32,
+    pub completed_at: u32,
+    /// Whether the owner had been inactive long enough for a real request.
+    pub timeout_elapsed: bool,
+    /// Whether the active owners could still reach the threshold with the
+    /// contact in the seat.
+    pub quorum_reachable: bool,
+}
+
+impl MultisigContract {
+    /// Records that `owner` just acted, which also voids any recovery request
+    /// against their seat.
+    pub(crate) fn record_activity(env: &Env, owner: &Address) {
+        env.storage().persistent().set(&(ACTIVITY_KEY, owner.clone()), &env.ledger().sequence());
+        env.storage().persistent().remove(&(RECOVERY_REQUEST_KEY, owner.clone()));
+    }
+
+    fn inactive_long_enough(env: &Env, owner: &Address, recovery: &RecoveryContact) -> bool {
+        let last_active = Self::get_last_activity(env.clone(), owner.clone());
+        env.ledger().sequence().saturating_sub(last_active) >= recovery.inactivity_timeout
+    }
+
+    /// Replaces `owner`'s seat with their recovery contact, keeping its position.
+    pub(crate) fn complete_recovery(env: &Env, owner: Address) -> Result<(), MultisigError> {
+        let recovery: RecoveryContact = env.storage().persistent().get(&(RECOVERY_KEY, owner.clone()))
+            .ok_or(MultisigError::RecoveryNotConfigured)?;
+        if !env.storage().persistent().has(&(RECOVERY_REQUEST_KEY, owner.clone())) {
+            return Err(MultisigError::RecoveryNotRequested);
+        }
+        if !Self::inactive_long_enough(env, &owner, &recovery) {
+            return Err(MultisigError::OwnerStillActive);
+        }
+
+        Self::replace_seat(env, &owner, &recovery.contact)?;
+        Self::publish_event(env, (symbol_short!("recovered"), owner), recovery.contact);
+
+        Ok(())
+    }
+
+    /// Runs `complete_recovery` for `owner`'s seat against a copy of the
+    /// signer set, ignoring the owner's activity, and records the outcome.
+    pub(crate) fn complete_rehearsal(env: &Env, owner: Address) -> Result<(), MultisigError> {
+        let recovery: RecoveryContact = env.storage().persistent().get(&(RECOVERY_KEY, owner.clone()))
+            .ok_or(MultisigError::RecoveryNotConfigured)?;
+        let requested_at: u32 = env.storage().persistent().get(&(REHEARSAL_REQUEST_KEY, owner.clone()))
+            .ok_or(MultisigError::RecoveryNotRequested)?;
+        let impact = Self::preview_config_change(env.clone(), ConfigChange::AcknowledgeRecovery(owner.clone()))?;
+
+        let rehearsal = RecoveryRehearsal {
+            contact: recovery.contact.clone(),
+            requested_at,
+            completed_at: env.ledger().sequence(),
+            timeout_elapsed: Self::inactive_long_enough(env, &owner, &recovery),
+            quorum_reachable: impact.quorum_reachable,
+        };
+        env.storage().persistent().remove(&(REHEARSAL_REQUEST_KEY, owner.clone()));
+        env.storage().persistent().set(&(REHEARSAL_KEY, owner.clone()), &rehearsal);
+        Self::publish_event(env, (symbol_short!("rehearsed"), owner), recovery.contact);
+
+        Ok(())
+    }
+
+    /// Hands `owner`'s seat, keeping its position, weight and role, to
+    /// `successor`, and drops any recovery and signer key set up for the old
+    /// seat. The old key's votes on pending proposals are withdrawn, so the
+    /// seat never counts twice.
+    pub(crate) fn replace_seat(env: &Env, owner: &Address, successor: &Address) -> Result<(), MultisigError> {
+        let mut config = Self::get_config(env)?;
+        if config.owners.contains(successor) {
+            return Err(MultisigError::DuplicateOwner);
+        }
+        let index = config.owners.first_index_of(owner).ok_or(MultisigError::InvalidOwner)?;
+        Self::void_pending_approvals(env, &config, owner, Self::owner_weight(&config, owner));
+        Self::void_pending_rejections(env, &config, owner);
+        config.owners.set(index, successor.clone());
+        Self::transfer_seat(&mut config, owner, successor);
+        Self::bump_epoch(env, &mut config)?;
+        env.storage().persistent().set(&CONFIG_KEY, &config);
+
+        env.storage().persistent().remove(&(RECOVERY_KEY, owner.clone()));
+        env.storage().persistent().remove(&(RECOVERY_REQUEST_KEY, owner.clone()));
+        Self::forget_signer_key(env, owner);
+        Self::record_activity(env, successor);
+        Self::refresh_quorum_alarm(env)?;
+
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Names the caller's recovery contact, replacing any earlier one.
+    pub fn set_recovery_contact(
+        env: Env,
+        caller: Address,
+        recovery: RecoveryContact,
+    ) -> Result<(), MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        if recovery.inactivity_timeout == 0 {
+            return Err(MultisigError::InvalidSchedule);
+        }
+        if recovery.contact == caller || Self::is_unusable_address(&env, &recovery.contact) {
+            return Err(MultisigError::InvalidAddress);
+        }
+
+        env.storage().persistent().set(&(RECOVERY_KEY, caller.clone()), &recovery);
+        Self::publish_event(&env, (symbol_short!("recov_set"), caller), recovery.contact);
+
+        Ok(())
+    }
+
+    pub fn remove_recovery_contact(env: Env, caller: Address) -> Result<(), MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        env.storage().persistent().remove(&(RECOVERY_KEY, caller.clone()));
+        Self::publish_event(&env, (symbol_short!("recov_del"), caller), ());
+
+        Ok(())
+    }
+
+    /// Called by a recovery contact once their owner has gone quiet. The seat
+    /// moves only after the quorum executes `AcknowledgeRecovery` for it, and
+    /// any activity from the owner in between voids the request.
+    pub fn request_recovery(env: Env, contact: Address, owner: Address) -> Result<(), MultisigError> {
+        contact.require_auth();
+        if !Self::get_config(&env)?.owners.contains(&owner) {
+            return Err(MultisigError::InvalidOwner);
+        }
+
+        let recovery: RecoveryContact = env.storage().persistent().get(&(RECOVERY_KEY, owner.clone()))
+            .ok_or(MultisigError::RecoveryNotConfigured)?;
+        if recovery.contact != contact {
+            return Err(MultisigError::Unauthorized);
+        }
+        if !Self::inactive_long_enough(&env, &owner, &recovery) {
+            return Err(MultisigError::OwnerStillActive);
+        }
+
+        env.storage().persistent().set(&(RECOVERY_REQUEST_KEY, owner.clone()), &env.ledger().sequence());
+        Self::publish_event(&env, (symbol_short!("recov_req"), owner), contact);
+
+        Ok(())
+    }
+
+    /// Starts a rehearsal of `request_recovery`. Unlike a real request it
+    /// does not wait for the owner to go quiet, and the owner's activity does
+    /// not void it; the quorum completes it with a `RehearseRecovery` change.
+    pub fn rehearse_recovery(env: Env, contact: Address, owner: Address) -> Result<(), MultisigError> {
+        contact.require_auth();
+        if !Self::get_config(&env)?.owners.contains(&owner) {
+            return Err(MultisigError::InvalidOwner);
+        }
+
+        let recovery: RecoveryContact = env.storage().persistent().get(&(RECOVERY_KEY, owner.clone()))
+            .ok_or(MultisigError::RecoveryNotConfigured)?;
+        if recovery.contact != contact {
+            return Err(MultisigError::Unauthorized);
+        }
+
+        env.storage().persistent().set(&(REHEARSAL_REQUEST_KEY, owner.clone()), &env.ledger().sequence());
+        Self::publish_event(&env, (symbol_short!("rehearse"), owner), contact);
+
+        Ok(())
+    }
+
+    /// The last completed rehearsal of recovering `owner`'s seat.
+    pub fn get_recovery_rehearsal(env: Env, owner: Address) -> Option<RecoveryRehearsal> {
+        env.storage().persistent().get(&(REHEARSAL_KEY, owner))
+    }
+
+    pub fn get_recovery_contact(env: Env, owner: Address) -> Option<RecoveryContact> {
+        env.storage().persistent().get(&(RECOVERY_KEY, owner))
+    }
+
+    /// Ledger of the owner's last authenticated action, or zero if none.
+    pub fn get_last_activity(env: Env, owner: Address) -> u32 {
+        env.storage().persistent().get(&(ACTIVITY_KEY, owner)).unwrap_or(0)
+    }
+}
@@ -0,0 +1,194 @@
+//! Wallet-managed token registry. Tokens the wallet knowingly holds are added
+//! by proposal, either directly or by a `WrapAsset` proposal that deploys the
+//! Stellar Asset Contract for a classic asset. Their metadata is cached so UIs
+//! can render the treasury from `get_portfolio` alone.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, token, Address, Bytes, BytesN, Env, String, Symbol, Vec};
+
+use crate::{
+    MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError, OracleAsset,
+    PriceOracleClient, TransactionKind,
+};
+
+pub(crate) const TOKEN_REGISTRY_KEY: Symbol = symbol_short!("tok_reg");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RegisteredToken {
+    pub token: Address,
+    /// Cached from the token contract when registered.
+    pub symbol: String,
+    pub decimals: u32,
+    /// Proposal that added the token.
+    pub added_by: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PortfolioEntry {
+    pub token: Address,
+    pub symbol: String,
+    pub decimals: u32,
+    pub balance: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenValue {
+    pub token: Address,
+    pub balance: i128,
+    /// Value of `balance` in the oracle's base asset, scaled by its
+    /// decimals; `None` when the oracle has no fresh price for the token.
+    pub value: Option<i128>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PortfolioValue {
+    pub tokens: Vec<TokenValue>,
+    /// Sum of every priced token's value.
+    pub total: i128,
+    /// Whether every registered token had a fresh price.
+    pub complete: bool,
+}
+
+impl MultisigContract {
+    /// Values the registered tokens at `oracle`'s latest prices, skipping
+    /// prices older than `max_price_age` seconds.
+    pub(crate) fn portfolio_value(
+        env: &Env,
+        oracle: &Address,
+        max_price_age: u64,
+    ) -> Result<PortfolioValue, MultisigError> {
+        let oracle = PriceOracleClient::new(env, oracle);
+        let this = env.current_contract_address();
+        let now = env.ledger().timestamp();
+
+        let mut tokens = Vec::new(env);
+        let mut total: i128 = 0;
+        let mut complete = true;
+        for entry in Self::get_token_registry(env.clone()).iter() {
+            let balance = token::Client::new(env, &entry.token).balance(&this);
+            let price = oracle.lastprice(&OracleAsset::Stellar(entry.token.clone()))
+                .filter(|price| price.price > 0 && now.saturating_sub(price.timestamp) <= max_price_age);
+
+            let value = match price {
+                Some(price) => {
+                    let scale = 10i128.checked_pow(entry.decimals).ok_or(MultisigError::ArithmeticError)?;
+                    let value = balance.checked_mul(price.price).ok_or(MultisigError::ArithmeticError)? / scale;
+                    total = total.checked_add(value).ok_or(MultisigError::ArithmeticError)?;
+                    Some(value)
+                },
+                None => {
+                    complete = false;
+                    None
+                },
+            };
+            tokens.push_back(TokenValue { token: entry.token, balance, value });
+        }
+
+        Ok(PortfolioValue { tokens, total, complete })
+    }
+
+    pub(crate) fn register_token(env: &Env, token: &Address, transaction_id: u32) {
+        let mut registry = Self::get_token_registry(env.clone());
+        if registry.iter().any(|entry| entry.token == *token) {
+            return;
+        }
+
+        registry.push_back(RegisteredToken {
+            token: token.clone(),
+            symbol: token::Client::new(env, token).symbol(),
+            decimals: Self::token_decimals(env, token, true),
+            added_by: transaction_id,
+        });
+        env.storage().persistent().set(&TOKEN_REGISTRY_KEY, &registry);
+        Self::track_asset(env, token);
+
+        Self::publish_event(env, (symbol_short!("tok_reg"), token.clone()), transaction_id);
+    }
+
+    pub(crate) fn deregister_token(env: &Env, token: &Address) {
+        let mut registry = Self::get_token_registry(env.clone());
+        if let Some(index) = registry.iter().position(|entry| entry.token == *token) {
+            registry.remove(index as u32);
+            env.storage().persistent().set(&TOKEN_REGISTRY_KEY, &registry);
+            Self::publish_event(env, (symbol_short!("tok_unreg"), token.clone()), ());
+        }
+    }
+
+    /// Deploys the SAC for `serialized_asset`, the XDR of a classic `Asset`,
+    /// and registers it. Fails if the asset has already been wrapped.
+    pub(crate) fn wrap_asset(
+        env: &Env,
+        transaction_id: u32,
+        serialized_asset: Bytes,
+    ) -> Result<Address, MultisigError> {
+        let token = env.deployer().with_stellar_asset(serialized_asset).deploy();
+        Self::register_token(env, &token, transaction_id);
+
+        Ok(token)
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Proposes wrapping a classic asset, given as its XDR-encoded `Asset`.
+    pub fn submit_wrap_asset(
+        env: Env,
+        caller: Address,
+        serialized_asset: Bytes,
+    ) -> Result<u32, MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        let token = Self::asset_contract_address(env.clone(), serialized_asset.clone());
+        let data = BytesN::from_array(&env, &[0; 32]);
+        Self::store_transaction(
+            &env,
+            &caller,
+            token.clone(),
+            0,
+            token,
+            data,
+            TransactionKind::WrapAsset(serialized_asset),
+        )
+    }
+
+    /// Tokens added to the registry, in the order they were added.
+    pub fn get_token_registry(env: Env) -> Vec<RegisteredToken> {
+        env.storage().persistent().get(&TOKEN_REGISTRY_KEY).unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Every registered token with its cached metadata and the wallet's
+    /// current balance of it.
+    pub fn get_portfolio(env: Env) -> Vec<PortfolioEntry> {
+        let this = env.current_contract_address();
+        let mut portfolio = Vec::new(&env);
+        for entry in Self::get_token_registry(env.clone()).iter() {
+            let balance = token::Client::new(&env, &entry.token).balance(&this);
+            portfolio.push_back(PortfolioEntry {
+                token: entry.token,
+                symbol: entry.symbol,
+                decimals: entry.decimals,
+                balance,
+            });
+        }
+        portfolio
+    }
+
+    /// Per-token and total value of the registered tokens, priced by `oracle`
+    /// in its base asset. Prices older than `max_price_age` seconds are left out.
+    pub fn get_portfolio_value(
+        env: Env,
+        oracle: Address,
+        max_price_age: u64,
+    ) -> Result<PortfolioValue, MultisigError> {
+        Self::portfolio_value(&env, &oracle, max_price_age)
+    }
+
+    /// Address the SAC for `serialized_asset` has or will have once wrapped.
+    pub fn asset_contract_address(env: Env, serialized_asset: Bytes) -> Address {
+        env.deployer().with_stellar_asset(serialized_asset).deployed_address()
+    }
+}
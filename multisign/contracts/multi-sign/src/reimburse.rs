@@ -0,0 +1,93 @@
+//! Optional flat reimbursement, paid from the wallet to whoever executes a
+//! proposal, for the network fees they covered. It comes out of the fee pool
+//! while that holds enough, and out of the free balance otherwise.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, token, Address, Env, Symbol};
+
+use crate::{MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError};
+
+const REIMBURSED_KEY: Symbol = symbol_short!("reimbursd");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Reimbursement {
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ExecutorReimbursement {
+    Disabled,
+    Enabled(Reimbursement),
+}
+
+impl MultisigContract {
+    pub(crate) fn validate_reimbursement(
+        env: &Env,
+        reimbursement: &ExecutorReimbursement,
+    ) -> Result<(), MultisigError> {
+        if let ExecutorReimbursement::Enabled(reimbursement) = reimbursement {
+            if reimbursement.amount <= 0 {
+                return Err(MultisigError::InvalidAmount);
+            }
+            if Self::is_unusable_address(env, &reimbursement.token) {
+                return Err(MultisigError::InvalidToken);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Pays the configured reimbursement to `executor`. A wallet that cannot
+    /// currently afford it skips the payment rather than blocking execution.
+    pub(crate) fn reimburse_executor(
+        env: &Env,
+        executor: &Address,
+        transaction_id: u32,
+    ) -> Result<(), MultisigError> {
+        let ExecutorReimbursement::Enabled(reimbursement) = Self::get_config(env)?.reimbursement else {
+            return Ok(());
+        };
+
+        let affordable = || {
+            Self::check_savings_lock(env, &reimbursement.token, reimbursement.amount, false).is_ok()
+                && Self::check_reserved_balance(env, &reimbursement.token, reimbursement.amount).is_ok()
+                && Self::ensure_token_not_paused(env, &reimbursement.token).is_ok()
+        };
+        let paid = Self::pay_from_fee_pool(env, &reimbursement.token, executor, reimbursement.amount)
+            || (affordable() && token::Client::new(env, &reimbursement.token)
+                .try_transfer(&env.current_contract_address(), executor, &reimbursement.amount)
+                .is_ok());
+
+        if !paid {
+            Self::publish_event(env, (symbol_short!("reimb_skp"), transaction_id), executor.clone());
+            return Ok(());
+        }
+
+        let key = (REIMBURSED_KEY, executor.clone(), reimbursement.token.clone());
+        let total: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        let total = total.checked_add(reimbursement.amount).ok_or(MultisigError::ArithmeticError)?;
+        env.storage().persistent().set(&key, &total);
+
+        Self::publish_event(
+            env,
+            (symbol_short!("reimburse"), transaction_id),
+            (executor.clone(), reimbursement.amount)
+        );
+
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    pub fn get_executor_reimbursement(env: Env) -> Result<ExecutorReimbursement, MultisigError> {
+        Ok(Self::get_config(&env)?.reimbursement)
+    }
+
+    /// Total of `token` reimbursed to `executor` so far.
+    pub fn get_reimbursed(env: Env, executor: Address, token: Address) -> i128 {
+        env.storage().persistent().get(&(REIMBURSED_KEY, executor, token)).unwrap_or(0)
+    }
+}
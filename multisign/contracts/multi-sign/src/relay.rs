@@ -0,0 +1,100 @@
+//! Relayed approvals. Owners sign approvals off-chain with their signer key,
+//! and anyone can submit a bundle of them, across owners and proposals, in a
+//! single call. A signature covers the proposal's content and the config
+//! epoch, and spends one of the owner's relay nonces, so it cannot be
+//! replayed once used, revoked or voided.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env, Symbol, Vec};
+
+use crate::{
+    MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError, SignerKey, SignerProof,
+    Transaction, TX_KEY,
+};
+
+pub(crate) const RELAY_NONCE_KEY: Symbol = symbol_short!("relay_nce");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignedApproval {
+    pub owner: Address,
+    pub transaction_id: u32,
+    /// The owner's next relay nonce, from `get_relay_nonce`.
+    pub nonce: u64,
+    /// Signature over `get_approval_message(transaction_id, nonce)` by the
+    /// owner's signer key.
+    pub proof: SignerProof,
+}
+
+impl MultisigContract {
+    fn approval_message(env: &Env, transaction_id: u32, nonce: u64) -> Result<Bytes, MultisigError> {
+        let transaction: Transaction = env.storage().persistent().get(&(TX_KEY, transaction_id))
+            .ok_or(MultisigError::TransactionNotFound)?;
+        let epoch = Self::get_config(env)?.epoch;
+        let content_hash = Self::proposal_hash(env, &transaction);
+
+        Ok((env.current_contract_address(), symbol_short!("approve"), transaction_id, content_hash, epoch, nonce)
+            .to_xdr(env))
+    }
+
+    /// Spends `owner`'s relay nonce, which must be the next one.
+    fn consume_relay_nonce(env: &Env, owner: &Address, nonce: u64) -> Result<(), MultisigError> {
+        let expected = Self::get_relay_nonce(env.clone(), owner.clone());
+        if nonce != expected {
+            return Err(MultisigError::Unauthorized);
+        }
+        let next = expected.checked_add(1).ok_or(MultisigError::ArithmeticError)?;
+        env.storage().persistent().set(&(RELAY_NONCE_KEY, owner.clone()), &next);
+
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Registers an ed25519 key that signs `caller`'s relayed approvals;
+    /// shorthand for `set_signer_key` with `SignerKey::Ed25519`.
+    pub fn set_approval_key(env: Env, caller: Address, public_key: BytesN<32>) -> Result<(), MultisigError> {
+        Self::set_signer_key(env, caller, SignerKey::Ed25519(public_key))
+    }
+
+    pub fn get_approval_key(env: Env, owner: Address) -> Option<BytesN<32>> {
+        match Self::get_signer_key(env, owner) {
+            SignerKey::Ed25519(public_key) => Some(public_key),
+            _ => None,
+        }
+    }
+
+    /// Bytes an owner signs to approve `transaction_id` through a relayer,
+    /// spending relay nonce `nonce`.
+    pub fn get_approval_message(env: Env, transaction_id: u32, nonce: u64) -> Result<Bytes, MultisigError> {
+        Self::approval_message(&env, transaction_id, nonce)
+    }
+
+    /// The nonce `owner`'s next relayed approval must carry.
+    pub fn get_relay_nonce(env: Env, owner: Address) -> u64 {
+        env.storage().persistent().get(&(RELAY_NONCE_KEY, owner)).unwrap_or(0)
+    }
+
+    /// Records a bundle of signed approvals. Any caller may relay them.
+    /// Approvals already on record are skipped, so a bundle can be retried;
+    /// any other invalid entry, including a stale or reused nonce, fails the
+    /// whole call. Returns how many approvals were added.
+    pub fn relay_approvals(env: Env, approvals: Vec<SignedApproval>) -> Result<u32, MultisigError> {
+        let mut recorded = 0;
+        for approval in approvals.iter() {
+            Self::verify_owner(&env, &approval.owner)?;
+            let message = Self::approval_message(&env, approval.transaction_id, approval.nonce)?;
+            Self::verify_signer(&env, &approval.owner, &message, &approval.proof)?;
+
+            if Self::has_approved(&env, approval.transaction_id, &approval.owner) {
+                continue;
+            }
+            Self::consume_relay_nonce(&env, &approval.owner, approval.nonce)?;
+
+            Self::record_approval(&env, &approval.owner, approval.transaction_id)?;
+            recorded += 1;
+        }
+
+        Ok(recorded)
+    }
+}
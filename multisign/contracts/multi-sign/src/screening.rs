@@ -0,0 +1,92 @@
+//! Recipient screening. The quorum may name a compliance contract that vets
+//! the recipient of every outgoing payment, once when it is submitted and
+//! again when it executes. A screener that declines, or fails to answer,
+//! vetoes the payment. Each passing screening is recorded against the proposal.
+
+use soroban_sdk::{contractclient, contractimpl, contracttype, symbol_short, Address, Env, Symbol};
+
+use crate::{
+    MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError, Transaction,
+    TransactionKind,
+};
+
+pub(crate) const SCREENER_KEY: Symbol = symbol_short!("screener");
+pub(crate) const SCREENING_KEY: Symbol = symbol_short!("screening");
+
+#[contractclient(name = "AddressScreenerClient")]
+pub trait AddressScreener {
+    /// Whether the wallet may pay `amount` of `token` to `recipient`.
+    fn screen(env: Env, wallet: Address, recipient: Address, token: Address, amount: i128) -> bool;
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScreeningRecord {
+    pub screener: Address,
+    /// Ledger of the submission-time screening, unless the screener was
+    /// only named afterwards.
+    pub submitted_at: Option<u32>,
+    /// Ledger of the execution-time screening, once the proposal has run.
+    pub executed_at: Option<u32>,
+}
+
+impl MultisigContract {
+    pub(crate) fn set_screener(env: &Env, screener: Option<Address>) -> Result<(), MultisigError> {
+        match &screener {
+            Some(screener) if Self::is_unusable_address(env, screener) => return Err(MultisigError::InvalidAddress),
+            Some(screener) => env.storage().persistent().set(&SCREENER_KEY, screener),
+            None => env.storage().persistent().remove(&SCREENER_KEY),
+        }
+        Self::publish_event(env, (symbol_short!("screener"),), screener);
+
+        Ok(())
+    }
+
+    /// Whether proposals of this kind pay out to their `to` address.
+    pub(crate) fn pays_recipient(kind: &TransactionKind) -> bool {
+        matches!(kind, TransactionKind::Transfer | TransactionKind::Claimable(_))
+    }
+
+    /// Has the screener vet the proposal's recipient. `executing` tells the
+    /// execution-time check from the submission-time one.
+    pub(crate) fn screen_recipient(
+        env: &Env,
+        transaction_id: u32,
+        transaction: &Transaction,
+        executing: bool,
+    ) -> Result<(), MultisigError> {
+        let Some(screener) = Self::get_screener(env.clone()) else {
+            return Ok(());
+        };
+
+        let cleared = AddressScreenerClient::new(env, &screener)
+            .try_screen(&env.current_contract_address(), &transaction.to, &transaction.token, &transaction.amount);
+        if !matches!(cleared, Ok(Ok(true))) {
+            return Err(MultisigError::RecipientNotAllowed);
+        }
+
+        let key = (SCREENING_KEY, transaction_id);
+        let mut record = env.storage().persistent().get::<_, ScreeningRecord>(&key)
+            .unwrap_or(ScreeningRecord { screener: screener.clone(), submitted_at: None, executed_at: None });
+        record.screener = screener;
+        if executing {
+            record.executed_at = Some(env.ledger().sequence());
+        } else {
+            record.submitted_at = Some(env.ledger().sequence());
+        }
+        env.storage().persistent().set(&key, &record);
+
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    pub fn get_screener(env: Env) -> Option<Address> {
+        env.storage().persistent().get(&SCREENER_KEY)
+    }
+
+    pub fn get_screening_record(env: Env, transaction_id: u32) -> Option<ScreeningRecord> {
+        env.storage().persistent().get(&(SCREENING_KEY, transaction_id))
+    }
+}
@@ -0,0 +1,108 @@
+//! Signer keys. Each owner seat is identified by its address, and signs
+//! off-chain messages, such as relayed approvals, with the scheme its signer
+//! key names. Verification is dispatched here, so a new scheme means a new
+//! variant rather than changes to the approval logic. Seats without a
+//! registered key sign as their address.
+
+use soroban_sdk::{
+    contractclient, contractimpl, contracttype, symbol_short, Address, Bytes, BytesN, Env, IntoVal, Symbol,
+};
+
+use crate::{MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError};
+
+pub(crate) const SIGNER_KEY: Symbol = symbol_short!("signer");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SignerKey {
+    /// The seat's own address authorizes the message.
+    Address(Address),
+    Ed25519(BytesN<32>),
+    /// Uncompressed SEC-1 public key; signatures are over the SHA-256 of the
+    /// message.
+    Secp256r1(BytesN<65>),
+    /// A contract that decides whether the seat signs a message.
+    Policy(Address),
+}
+
+/// Evidence that a signer key signed a message, matching its scheme.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SignerProof {
+    /// Supplied through the address's own authorization of the call.
+    Address,
+    Ed25519(BytesN<64>),
+    Secp256r1(BytesN<64>),
+    /// The policy contract is asked at verification time.
+    Policy,
+}
+
+#[contractclient(name = "SignerPolicyClient")]
+pub trait SignerPolicy {
+    /// Whether the policy signs `message` on behalf of `owner`'s seat.
+    fn check(env: Env, wallet: Address, owner: Address, message: Bytes) -> bool;
+}
+
+impl MultisigContract {
+    /// Fails unless `proof` shows `owner`'s signer key signed `message`.
+    pub(crate) fn verify_signer(
+        env: &Env,
+        owner: &Address,
+        message: &Bytes,
+        proof: &SignerProof,
+    ) -> Result<(), MultisigError> {
+        match (Self::get_signer_key(env.clone(), owner.clone()), proof) {
+            (SignerKey::Address(address), SignerProof::Address) => {
+                address.require_auth_for_args((message.clone(),).into_val(env));
+            },
+            (SignerKey::Ed25519(public_key), SignerProof::Ed25519(signature)) => {
+                env.crypto().ed25519_verify(&public_key, message, signature);
+            },
+            (SignerKey::Secp256r1(public_key), SignerProof::Secp256r1(signature)) => {
+                env.crypto().secp256r1_verify(&public_key, &env.crypto().sha256(message), signature);
+            },
+            (SignerKey::Policy(policy), SignerProof::Policy) => {
+                let signed = SignerPolicyClient::new(env, &policy)
+                    .check(&env.current_contract_address(), owner, message);
+                if !signed {
+                    return Err(MultisigError::Unauthorized);
+                }
+            },
+            _ => return Err(MultisigError::Unauthorized),
+        }
+
+        Ok(())
+    }
+
+    /// Drops the signer key of a seat `owner` is leaving. Their relay nonce
+    /// stays, so messages they signed cannot be replayed if they are seated
+    /// again.
+    pub(crate) fn forget_signer_key(env: &Env, owner: &Address) {
+        env.storage().persistent().remove(&(SIGNER_KEY, owner.clone()));
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Sets the key `caller`'s seat signs with, replacing any earlier one.
+    pub fn set_signer_key(env: Env, caller: Address, key: SignerKey) -> Result<(), MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        if let SignerKey::Address(address) | SignerKey::Policy(address) = &key {
+            if Self::is_unusable_address(&env, address) {
+                return Err(MultisigError::InvalidAddress);
+            }
+        }
+
+        env.storage().persistent().set(&(SIGNER_KEY, caller.clone()), &key);
+        Self::publish_event(&env, (symbol_short!("signer"), caller), key);
+
+        Ok(())
+    }
+
+    /// The seat's signer key; its own address unless another was registered.
+    pub fn get_signer_key(env: Env, owner: Address) -> SignerKey {
+        env.storage().persistent().get(&(SIGNER_KEY, owner.clone())).unwrap_or(SignerKey::Address(owner))
+    }
+}
@@ -0,0 +1,122 @@
+//! Simulation locks. The submitter may pin a proposal to the balance changes
+//! an off-chain simulation predicted for the wallet. Approvers compare the
+//! lock's hash with their own simulation, and execution aborts if the real
+//! changes drift beyond the tolerance.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, token, xdr::ToXdr, Address, BytesN, Env, Symbol, Vec};
+
+use crate::{
+    MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError, Transaction,
+    TX_KEY,
+};
+
+pub(crate) const SIMULATION_KEY: Symbol = symbol_short!("sim_lock");
+
+const BPS_DENOMINATOR: i128 = 10_000;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BalanceDelta {
+    pub token: Address,
+    /// Expected change in the wallet's balance; negative for outflows.
+    pub delta: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SimulationLock {
+    /// SHA-256 of the XDR-encoded `deltas`.
+    pub hash: BytesN<32>,
+    pub deltas: Vec<BalanceDelta>,
+    /// Allowed divergence from each expected delta, in basis points of it.
+    pub tolerance_bps: u32,
+}
+
+impl MultisigContract {
+    /// Wallet balances of the locked tokens, taken just before execution.
+    pub(crate) fn snapshot_simulated_balances(env: &Env, transaction_id: u32) -> Option<Vec<i128>> {
+        let lock: SimulationLock = env.storage().persistent().get(&(SIMULATION_KEY, transaction_id))?;
+        let this = env.current_contract_address();
+
+        let mut balances = Vec::new(env);
+        for expected in lock.deltas.iter() {
+            balances.push_back(token::Client::new(env, &expected.token).balance(&this));
+        }
+        Some(balances)
+    }
+
+    pub(crate) fn verify_simulation(
+        env: &Env,
+        transaction_id: u32,
+        balances_before: Option<Vec<i128>>,
+    ) -> Result<(), MultisigError> {
+        let Some(balances_before) = balances_before else {
+            return Ok(());
+        };
+        let lock: SimulationLock = env.storage().persistent().get(&(SIMULATION_KEY, transaction_id))
+            .ok_or(MultisigError::SimulationMismatch)?;
+        let this = env.current_contract_address();
+
+        for (expected, before) in lock.deltas.iter().zip(balances_before.iter()) {
+            let after = token::Client::new(env, &expected.token).balance(&this);
+            let actual = after.checked_sub(before).ok_or(MultisigError::ArithmeticError)?;
+            let tolerance = expected.delta.checked_abs()
+                .and_then(|magnitude| magnitude.checked_mul(lock.tolerance_bps as i128))
+                .ok_or(MultisigError::ArithmeticError)?
+                / BPS_DENOMINATOR;
+            let divergence = actual.checked_sub(expected.delta)
+                .and_then(i128::checked_abs)
+                .ok_or(MultisigError::ArithmeticError)?;
+            if divergence > tolerance {
+                return Err(MultisigError::SimulationMismatch);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Locks a proposal to the balance changes its simulation predicted. Only
+    /// the submitter may do this, and only before anyone else has voted.
+    /// Returns the lock hash for approvers to check against.
+    pub fn attach_simulation(
+        env: Env,
+        caller: Address,
+        transaction_id: u32,
+        deltas: Vec<BalanceDelta>,
+        tolerance_bps: u32,
+    ) -> Result<BytesN<32>, MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        let transaction: Transaction = env.storage().persistent().get(&(TX_KEY, transaction_id))
+            .ok_or(MultisigError::TransactionNotFound)?;
+        Self::ensure_pending(&env, &transaction)?;
+        if caller != transaction.submitter {
+            return Err(MultisigError::Unauthorized);
+        }
+
+        let config = Self::get_config(&env)?;
+        let approvals = Self::approvers(&env, &config, transaction_id);
+        if transaction.rejections > 0 || approvals.iter().any(|approver| approver != caller) {
+            return Err(MultisigError::AlreadyApproved);
+        }
+        if deltas.is_empty() || tolerance_bps as i128 > BPS_DENOMINATOR {
+            return Err(MultisigError::InvalidMetadata);
+        }
+
+        let hash: BytesN<32> = env.crypto().sha256(&deltas.clone().to_xdr(&env)).into();
+        let lock = SimulationLock { hash: hash.clone(), deltas, tolerance_bps };
+        env.storage().persistent().set(&(SIMULATION_KEY, transaction_id), &lock);
+
+        Self::publish_event(&env, (symbol_short!("sim_lock"), transaction_id), hash.clone());
+
+        Ok(hash)
+    }
+
+    pub fn get_simulation_lock(env: Env, transaction_id: u32) -> Option<SimulationLock> {
+        env.storage().persistent().get(&(SIMULATION_KEY, transaction_id))
+    }
+}
@@ -0,0 +1,89 @@
+//! Slates: named consent agendas of routine proposals. An owner groups pending
+//! proposals under a name, and each owner then approves the whole slate in one
+//! action. Approval is all-or-none: if any member cannot take the caller's
+//! approval, none of them does.
+
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, symbol_short, Address, Env, Symbol, Vec};
+
+use crate::{
+    ExtendedError, MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError, Transaction, TX_KEY,
+};
+
+const SLATE_KEY: Symbol = symbol_short!("slate");
+
+const MAX_SLATE_LEN: u32 = 20;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Slate {
+    pub creator: Address,
+    pub transaction_ids: Vec<u32>,
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Groups up to 20 pending proposals under `name`. Names are unique
+    /// until the slate is withdrawn.
+    pub fn create_slate(
+        env: Env,
+        caller: Address,
+        name: Symbol,
+        transaction_ids: Vec<u32>,
+    ) -> Result<(), MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        let key = (SLATE_KEY, name.clone());
+        if env.storage().persistent().has(&key) {
+            panic_with_error!(&env, ExtendedError::SlateNameTaken);
+        }
+        if transaction_ids.is_empty() || transaction_ids.len() > MAX_SLATE_LEN {
+            return Err(MultisigError::InvalidMetadata);
+        }
+        for (index, transaction_id) in transaction_ids.iter().enumerate() {
+            if transaction_ids.first_index_of(transaction_id) != Some(index as u32) {
+                panic_with_error!(&env, ExtendedError::DuplicateSlateEntry);
+            }
+            let transaction: Transaction = env.storage().persistent().get(&(TX_KEY, transaction_id))
+                .ok_or(MultisigError::TransactionNotFound)?;
+            Self::ensure_pending(&env, &transaction)?;
+        }
+
+        env.storage().persistent().set(&key, &Slate { creator: caller.clone(), transaction_ids: transaction_ids.clone() });
+        Self::publish_event(&env, (symbol_short!("slate"), name), (caller, transaction_ids));
+
+        Ok(())
+    }
+
+    /// Approves every proposal on the slate, or none of them if any fails.
+    /// Returns how many were approved.
+    pub fn approve_slate(env: Env, caller: Address, name: Symbol) -> Result<u32, MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        let slate = Self::get_slate(env.clone(), name.clone()).ok_or(MultisigError::TransactionNotFound)?;
+        for transaction_id in slate.transaction_ids.iter() {
+            Self::record_approval(&env, &caller, transaction_id)?;
+        }
+        Self::publish_event(&env, (symbol_short!("slate_ok"), name), caller);
+
+        Ok(slate.transaction_ids.len())
+    }
+
+    /// Drops the slate; its proposals are left as they are.
+    pub fn withdraw_slate(env: Env, caller: Address, name: Symbol) -> Result<(), MultisigError> {
+        caller.require_auth();
+        let slate = Self::get_slate(env.clone(), name.clone()).ok_or(MultisigError::TransactionNotFound)?;
+        if caller != slate.creator {
+            return Err(MultisigError::Unauthorized);
+        }
+        env.storage().persistent().remove(&(SLATE_KEY, name.clone()));
+        Self::publish_event(&env, (symbol_short!("slate_del"), name), caller);
+
+        Ok(())
+    }
+
+    pub fn get_slate(env: Env, name: Symbol) -> Option<Slate> {
+        env.storage().persistent().get(&(SLATE_KEY, name))
+    }
+}
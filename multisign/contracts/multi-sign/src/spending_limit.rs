@@ -0,0 +1,99 @@
+//! Per-token spending limits. For each token the quorum may set a daily
+//! allowance that transfers can draw on with fewer approvals than the
+//! threshold, such as 1-of-N for routine payments. Transfers that would take
+//! the day's total past the allowance need the full threshold. Usage is kept
+//! per token and wallet period (a UTC day by default).
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, Env, Symbol};
+
+use crate::{
+    MultisigConfig, MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError,
+    Transaction, TransactionKind,
+};
+
+const SPENDING_LIMIT_KEY: Symbol = symbol_short!("spend_lim");
+const SPENDING_USAGE_KEY: Symbol = symbol_short!("spend_use");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendingPolicy {
+    /// Approvals a transfer within the allowance needs.
+    pub threshold: u32,
+    /// Total that may move on the reduced threshold per wallet period.
+    pub daily_limit: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SpendingLimit {
+    Removed,
+    Limited(SpendingPolicy),
+}
+
+impl MultisigContract {
+    pub(crate) fn set_spending_limit(env: &Env, token: Address, limit: SpendingLimit) -> Result<(), MultisigError> {
+        let key = (SPENDING_LIMIT_KEY, token.clone());
+        match &limit {
+            SpendingLimit::Limited(policy) => {
+                let config = Self::get_config(env)?;
+                if policy.threshold == 0 || policy.threshold > config.required_approvals {
+                    return Err(MultisigError::InvalidThreshold);
+                }
+                if policy.daily_limit <= 0 {
+                    return Err(MultisigError::InvalidAmount);
+                }
+                env.storage().persistent().set(&key, policy);
+            },
+            SpendingLimit::Removed => env.storage().persistent().remove(&key),
+        }
+        Self::publish_event(env, (symbol_short!("spend_lim"), token), limit);
+
+        Ok(())
+    }
+
+    /// The reduced threshold `transaction` qualifies for, if its token has a
+    /// limit with room left today.
+    pub(crate) fn spending_limit_threshold(env: &Env, transaction: &Transaction) -> Option<u32> {
+        if transaction.kind != TransactionKind::Transfer {
+            return None;
+        }
+        let policy = Self::get_spending_limit(env.clone(), transaction.token.clone())?;
+        let spent = Self::get_spent_today(env.clone(), transaction.token.clone());
+        spent.checked_add(transaction.amount)
+            .is_some_and(|total| total <= policy.daily_limit)
+            .then_some(policy.threshold)
+    }
+
+    /// Charges a transfer executed below the standard threshold to whichever
+    /// allowance let it through.
+    pub(crate) fn record_reduced_threshold_spend(
+        env: &Env,
+        config: &MultisigConfig,
+        transaction: &Transaction,
+    ) -> Result<(), MultisigError> {
+        if Self::fast_lane_applies(env, config, transaction) {
+            return Self::record_fast_lane_spend(env, transaction.amount);
+        }
+
+        let spent = Self::get_spent_today(env.clone(), transaction.token.clone())
+            .checked_add(transaction.amount)
+            .ok_or(MultisigError::ArithmeticError)?;
+        let key = (SPENDING_USAGE_KEY, transaction.token.clone(), Self::current_period(env));
+        env.storage().persistent().set(&key, &spent);
+
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    pub fn get_spending_limit(env: Env, token: Address) -> Option<SpendingPolicy> {
+        env.storage().persistent().get(&(SPENDING_LIMIT_KEY, token))
+    }
+
+    /// Amount of `token` moved on its reduced threshold during the current
+    /// wallet period.
+    pub fn get_spent_today(env: Env, token: Address) -> i128 {
+        env.storage().persistent().get(&(SPENDING_USAGE_KEY, token, Self::current_period(&env))).unwrap_or(0)
+    }
+}
@@ -0,0 +1,144 @@
+//! Execution timelock for high-value proposals. Once a proposal of at least
+//! the configured amount reaches quorum, it can only be executed after a
+//! delay, giving owners and observers a window to react before funds move.
+//! The clock starts on the approval that meets the threshold, after a config
+//! change that brings a proposal to quorum, or through `record_quorum` when
+//! it got there some other way. Until then a covered proposal cannot execute.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Env, Error, Symbol};
+
+use crate::{
+    ExtendedError, MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError, Transaction, TX_KEY,
+};
+
+pub(crate) const TIMELOCK_KEY: Symbol = symbol_short!("timelock");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimelockPolicy {
+    /// Ledgers between reaching quorum and the earliest execution.
+    pub duration: u32,
+    /// Smallest amount a proposal moves, across all its legs, that the delay
+    /// applies to; zero covers every proposal.
+    pub min_amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Timelock {
+    Disabled,
+    Enabled(TimelockPolicy),
+}
+
+impl MultisigContract {
+    pub(crate) fn set_timelock(env: &Env, timelock: Timelock) -> Result<(), MultisigError> {
+        match &timelock {
+            Timelock::Enabled(policy) if policy.duration == 0 => return Err(MultisigError::InvalidSchedule),
+            Timelock::Enabled(policy) if policy.min_amount < 0 => return Err(MultisigError::InvalidAmount),
+            Timelock::Enabled(_) => env.storage().persistent().set(&TIMELOCK_KEY, &timelock),
+            Timelock::Disabled => env.storage().persistent().remove(&TIMELOCK_KEY),
+        }
+        Self::publish_event(env, (symbol_short!("timelock"),), timelock);
+
+        Ok(())
+    }
+
+    /// The delay that applies to `transaction`, if the timelock covers it.
+    fn timelock_duration(env: &Env, transaction: &Transaction) -> Option<u32> {
+        match Self::get_timelock(env.clone()) {
+            Timelock::Enabled(policy) if Self::moved_amount(env, transaction) >= policy.min_amount => {
+                Some(policy.duration)
+            },
+            _ => None,
+        }
+    }
+
+    /// Ledger from which the proposal may execute, once it has reached quorum.
+    fn execution_eta(env: &Env, transaction: &Transaction) -> Option<u32> {
+        let approved_at = transaction.approved_at?;
+        let duration = Self::timelock_duration(env, transaction).unwrap_or(0);
+
+        Some(approved_at.saturating_add(duration))
+    }
+
+    /// Rejects executing a proposal whose timelock has not run out, or has
+    /// not started, with `EarlyExecution`.
+    pub(crate) fn ensure_timelock_elapsed(env: &Env, transaction: &Transaction) -> Result<(), Error> {
+        if Self::timelock_duration(env, transaction).is_none() {
+            return Ok(());
+        }
+        match Self::execution_eta(env, transaction) {
+            Some(eta) if env.ledger().sequence() >= eta => Ok(()),
+            _ => Err(ExtendedError::EarlyExecution.into()),
+        }
+    }
+
+    /// Brings the quorum state of a pending proposal in line with what it
+    /// needs now: one that meets its requirement without a recorded quorum
+    /// reaches it, and one that no longer does loses its clock and
+    /// certificate.
+    pub(crate) fn sync_quorum(
+        env: &Env,
+        transaction_id: u32,
+        transaction: &mut Transaction,
+    ) -> Result<(), MultisigError> {
+        let config = Self::get_config(env)?;
+        let approvals = Self::effective_approvals(env, transaction_id, transaction);
+        let reached = approvals >= Self::required_approvals_for(env, &config, transaction);
+        match (reached, transaction.approved_at) {
+            (true, None) => {
+                Self::publish_quorum_reached(env, &config, transaction_id, transaction, approvals);
+                transaction.approved_at = Some(env.ledger().sequence());
+            },
+            (false, Some(_)) => {
+                transaction.approved_at = None;
+                env.storage().persistent().set(&(TX_KEY, transaction_id), transaction);
+                Self::withdraw_quorum_certificate(env, transaction_id);
+            },
+            _ => {},
+        }
+
+        Ok(())
+    }
+
+    /// Runs `sync_quorum` over every pending proposal, after a config change
+    /// that may have moved their requirements.
+    pub(crate) fn sync_pending_quorums(env: &Env) -> Result<(), MultisigError> {
+        for id in Self::get_pending_transactions(env.clone()).iter() {
+            if let Some(mut transaction) = env.storage().persistent().get::<_, Transaction>(&(TX_KEY, id)) {
+                Self::sync_quorum(env, id, &mut transaction)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    pub fn get_timelock(env: Env) -> Timelock {
+        env.storage().persistent().get(&TIMELOCK_KEY).unwrap_or(Timelock::Disabled)
+    }
+
+    /// Earliest ledger at which the proposal may be executed, or `None`
+    /// while it has not reached quorum.
+    pub fn get_execution_eta(env: Env, transaction_id: u32) -> Result<Option<u32>, MultisigError> {
+        let transaction: Transaction = env.storage().persistent().get(&(TX_KEY, transaction_id))
+            .ok_or(MultisigError::TransactionNotFound)?;
+
+        Ok(Self::execution_eta(&env, &transaction))
+    }
+
+    /// Records that a pending proposal has reached quorum without an approval
+    /// taking it there, e.g. once a spending-limit period rolls over, starting
+    /// its timelock. Anyone may call it. Returns the execution ETA, or `None`
+    /// if the proposal is still short of quorum.
+    pub fn record_quorum(env: Env, transaction_id: u32) -> Result<Option<u32>, MultisigError> {
+        let mut transaction: Transaction = env.storage().persistent().get(&(TX_KEY, transaction_id))
+            .ok_or(MultisigError::TransactionNotFound)?;
+        Self::ensure_pending(&env, &transaction)?;
+        Self::sync_quorum(&env, transaction_id, &mut transaction)?;
+
+        Ok(Self::execution_eta(&env, &transaction))
+    }
+}
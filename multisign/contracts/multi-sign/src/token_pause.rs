@@ -0,0 +1,56 @@
+//! Per-token pause switches. Any single owner can halt outflows of one token,
+//! e.g. during a depeg or an incident on its contract, without freezing the
+//! rest of the wallet. Resuming takes an approved `UnpauseToken` proposal.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, Env, Symbol};
+
+use crate::{MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError};
+
+const TOKEN_PAUSE_KEY: Symbol = symbol_short!("tok_pause");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenPause {
+    pub paused_by: Address,
+    pub paused_at: u32,
+}
+
+impl MultisigContract {
+    pub(crate) fn ensure_token_not_paused(env: &Env, token: &Address) -> Result<(), MultisigError> {
+        if Self::is_token_paused(env.clone(), token.clone()) {
+            return Err(MultisigError::LockedDown);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn unpause_token(env: &Env, token: Address) {
+        env.storage().persistent().remove(&(TOKEN_PAUSE_KEY, token.clone()));
+        Self::publish_event(env, (symbol_short!("tok_resum"), token), ());
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Halts outflows of `token` immediately. Proposals moving it can still be
+    /// voted on but fail to execute until it is unpaused.
+    pub fn pause_token(env: Env, caller: Address, token: Address) -> Result<(), MultisigError> {
+        caller.require_auth();
+        Self::verify_owner(&env, &caller)?;
+
+        let pause = TokenPause { paused_by: caller.clone(), paused_at: env.ledger().sequence() };
+        env.storage().persistent().set(&(TOKEN_PAUSE_KEY, token.clone()), &pause);
+
+        Self::publish_event(&env, (symbol_short!("tok_pause"), token), caller);
+
+        Ok(())
+    }
+
+    pub fn is_token_paused(env: Env, token: Address) -> bool {
+        env.storage().persistent().has(&(TOKEN_PAUSE_KEY, token))
+    }
+
+    pub fn get_token_pause(env: Env, token: Address) -> Option<TokenPause> {
+        env.storage().persistent().get(&(TOKEN_PAUSE_KEY, token))
+    }
+}
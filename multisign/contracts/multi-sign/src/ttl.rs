@@ -0,0 +1,224 @@
+//! Time-to-live upkeep. The instance, config and other wallet-wide entries are
+//! extended whenever the config is read, and a proposal's entries whenever it is
+//! voted on or executed, once they run low. Operators can top up long-lived
+//! proposals explicitly with `bump_storage`. Contracts cannot read TTLs
+//! on-chain, so the wallet records how far it last extended the core entries.
+
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, symbol_short, Env, Symbol, Vec};
+
+use crate::{
+    allowance::ALLOWANCES_KEY, amm::TRACKED_ASSETS_KEY, approval_cap::APPROVAL_CAP_KEY, archive::ARCHIVE_KEY,
+    bond::BOND_KEY, certificate::CERTIFICATE_KEY, checklist::ATTESTATION_KEY, claim::CLAIMABLE_KEY,
+    config_schedule::EFFECTIVE_AT_KEY, contract_call::CALL_RESULT_KEY, dca::DCA_COUNT_KEY, event_label::LABEL_KEY,
+    listener::LISTENER_KEY, lockdown::LOCKDOWN_KEY, ordering::{ORDER_HEAD_KEY, STRICT_ORDER_KEY},
+    owners::OWNER_LABEL_KEY, passkey::{KEY_LOSS_NONCE_KEY, PASSKEY_KEY}, pause::{PAUSED_KEY, PAUSERS_KEY},
+    payment_request::PAYMENT_REQUEST_COUNT_KEY, periods::{CALENDAR_KEY, PERIOD_ANCHOR_KEY},
+    policy_signer::POLICY_SIGNER_KEY, priority::URGENT_POLICY_KEY, quorum::QUORUM_ALARM_KEY,
+    receipt::{RECEIPT_KEY, RECEIPT_SEQ_KEY}, recovery::ACTIVITY_KEY, registry::TOKEN_REGISTRY_KEY,
+    relay::RELAY_NONCE_KEY, screening::{SCREENER_KEY, SCREENING_KEY}, signers::SIGNER_KEY, simulation::SIMULATION_KEY,
+    timelock::TIMELOCK_KEY, ExtendedError, MultisigConfig, MultisigContract, MultisigContractArgs,
+    MultisigContractClient, MultisigError, APPROVAL_KEY, CONFIG_KEY, PENDING_INDEX_KEY, RECURRING_COUNT_KEY,
+    REJECTION_KEY, REJECTION_REASON_KEY, SIGNER_META_KEY, TX_KEY,
+};
+
+const TTL_UNTIL_KEY: Symbol = symbol_short!("ttl_until");
+const TTL_SETTINGS_KEY: Symbol = symbol_short!("ttl_cfg");
+
+const DAY_IN_LEDGERS: u32 = 17_280;
+const CORE_TTL_THRESHOLD: u32 = 7 * DAY_IN_LEDGERS;
+const CORE_TTL_EXTEND_TO: u32 = 30 * DAY_IN_LEDGERS;
+const MAX_BUMP_IDS: u32 = 50;
+
+/// Wallet-wide persistent entries, kept alive with the config.
+const CORE_KEYS: [Symbol; 23] = [
+    CONFIG_KEY,
+    PENDING_INDEX_KEY,
+    RECURRING_COUNT_KEY,
+    ALLOWANCES_KEY,
+    TRACKED_ASSETS_KEY,
+    DCA_COUNT_KEY,
+    LABEL_KEY,
+    LISTENER_KEY,
+    LOCKDOWN_KEY,
+    STRICT_ORDER_KEY,
+    ORDER_HEAD_KEY,
+    PAUSED_KEY,
+    PAUSERS_KEY,
+    PAYMENT_REQUEST_COUNT_KEY,
+    CALENDAR_KEY,
+    PERIOD_ANCHOR_KEY,
+    POLICY_SIGNER_KEY,
+    URGENT_POLICY_KEY,
+    QUORUM_ALARM_KEY,
+    RECEIPT_SEQ_KEY,
+    TOKEN_REGISTRY_KEY,
+    SCREENER_KEY,
+    TIMELOCK_KEY,
+];
+
+/// Entries keyed by a seat's address, kept alive with the config while the
+/// seat is held.
+const OWNER_KEYS: [Symbol; 7] = [
+    SIGNER_KEY,
+    SIGNER_META_KEY,
+    PASSKEY_KEY,
+    OWNER_LABEL_KEY,
+    ACTIVITY_KEY,
+    RELAY_NONCE_KEY,
+    KEY_LOSS_NONCE_KEY,
+];
+
+/// Entries keyed by a proposal id besides the proposal and its votes.
+const TRANSACTION_KEYS: [Symbol; 13] = [
+    REJECTION_KEY,
+    REJECTION_REASON_KEY,
+    CERTIFICATE_KEY,
+    CALL_RESULT_KEY,
+    RECEIPT_KEY,
+    APPROVAL_CAP_KEY,
+    BOND_KEY,
+    CLAIMABLE_KEY,
+    ATTESTATION_KEY,
+    EFFECTIVE_AT_KEY,
+    SCREENING_KEY,
+    SIMULATION_KEY,
+    ARCHIVE_KEY,
+];
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct StorageTtl {
+    /// Ledgers left on an entry below which it is extended.
+    pub threshold: u32,
+    /// Ledgers an extended entry lives for from the current one.
+    pub extend_to: u32,
+}
+
+impl MultisigContract {
+    /// Extends the instance, config and other wallet-wide entries once fewer
+    /// than the configured threshold of ledgers remain on them.
+    pub(crate) fn extend_core_ttl(env: &Env) {
+        if !Self::core_ttl_due(env) {
+            return;
+        }
+
+        Self::extend_core_entries(env, Self::get_storage_ttl(env.clone()).extend_to);
+    }
+
+    fn extend_core_entries(env: &Env, extend_to: u32) {
+        env.storage().instance().extend_ttl(extend_to, extend_to);
+        let storage = env.storage().persistent();
+        for key in CORE_KEYS {
+            if storage.has(&key) {
+                storage.extend_ttl(&key, extend_to, extend_to);
+            }
+        }
+        if let Some(config) = storage.get::<_, MultisigConfig>(&CONFIG_KEY) {
+            for owner in config.owners.iter() {
+                for key in OWNER_KEYS {
+                    let owner_key = (key, owner.clone());
+                    if storage.has(&owner_key) {
+                        storage.extend_ttl(&owner_key, extend_to, extend_to);
+                    }
+                }
+            }
+        }
+        env.storage().instance().set(&TTL_UNTIL_KEY, &env.ledger().sequence().saturating_add(extend_to));
+    }
+
+    /// Whether the core entries are within the configured threshold of
+    /// expiring.
+    pub(crate) fn core_ttl_due(env: &Env) -> bool {
+        Self::core_ttl_remaining(env) < Self::get_storage_ttl(env.clone()).threshold
+    }
+
+    /// Ledgers left before the core entries expire, as far as the wallet
+    /// itself extended them. External extensions only make the real figure larger.
+    pub(crate) fn core_ttl_remaining(env: &Env) -> u32 {
+        let until: u32 = env.storage().instance().get(&TTL_UNTIL_KEY).unwrap_or(0);
+        until.saturating_sub(env.ledger().sequence())
+    }
+
+    /// Extends a proposal's transaction, approval, rejection and other side
+    /// entries to `extend_to` ledgers if fewer than `threshold` remain on them.
+    pub(crate) fn extend_transaction_ttl(
+        env: &Env,
+        config: &MultisigConfig,
+        transaction_id: u32,
+        threshold: u32,
+        extend_to: u32,
+    ) {
+        let storage = env.storage().persistent();
+        storage.extend_ttl(&(TX_KEY, transaction_id), threshold, extend_to);
+        let mut voters = config.owners.clone();
+        if let Some(policy) = Self::get_policy_signer(env.clone()) {
+            voters.push_back(policy);
+        }
+        for voter in voters.iter() {
+            let approval_key = (APPROVAL_KEY, transaction_id, voter);
+            if storage.has(&approval_key) {
+                storage.extend_ttl(&approval_key, threshold, extend_to);
+            }
+        }
+        for key in TRANSACTION_KEYS {
+            if storage.has(&(key.clone(), transaction_id)) {
+                storage.extend_ttl(&(key, transaction_id), threshold, extend_to);
+            }
+        }
+    }
+
+    /// `extend_transaction_ttl` with the configured settings, for the
+    /// proposal an operation just touched.
+    pub(crate) fn touch_transaction(env: &Env, config: &MultisigConfig, transaction_id: u32) {
+        let StorageTtl { threshold, extend_to } = Self::get_storage_ttl(env.clone());
+        Self::extend_transaction_ttl(env, config, transaction_id, threshold, extend_to);
+    }
+
+    /// The threshold must be positive and below `extend_to`, which the
+    /// network's maximum TTL caps.
+    pub(crate) fn set_storage_ttl(env: &Env, settings: StorageTtl) -> Result<(), MultisigError> {
+        if settings.threshold == 0
+            || settings.threshold >= settings.extend_to
+            || settings.extend_to > env.storage().max_ttl()
+        {
+            return Err(MultisigError::InvalidSchedule);
+        }
+
+        env.storage().instance().set(&TTL_SETTINGS_KEY, &settings);
+        env.storage().instance().remove(&TTL_UNTIL_KEY);
+        Self::extend_core_ttl(env);
+        Self::publish_event(env, (symbol_short!("ttl_cfg"),), (settings.threshold, settings.extend_to));
+
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Permissionless upkeep: extends the core entries and those of up to 50
+    /// proposals to the full configured TTL, however much they have left.
+    pub fn bump_storage(env: Env, tx_ids: Vec<u32>) -> Result<(), MultisigError> {
+        if tx_ids.len() > MAX_BUMP_IDS {
+            panic_with_error!(&env, ExtendedError::TooManyIds);
+        }
+        let config = Self::get_config(&env)?;
+        let StorageTtl { extend_to, .. } = Self::get_storage_ttl(env.clone());
+        for transaction_id in tx_ids.iter() {
+            if !env.storage().persistent().has(&(TX_KEY, transaction_id)) {
+                return Err(MultisigError::TransactionNotFound);
+            }
+            Self::extend_transaction_ttl(&env, &config, transaction_id, extend_to, extend_to);
+        }
+        Self::extend_core_entries(&env, extend_to);
+
+        Ok(())
+    }
+
+    /// When and how far entries are extended; a week and 30 days by default.
+    pub fn get_storage_ttl(env: Env) -> StorageTtl {
+        env.storage().instance().get(&TTL_SETTINGS_KEY).unwrap_or(StorageTtl {
+            threshold: CORE_TTL_THRESHOLD,
+            extend_to: CORE_TTL_EXTEND_TO,
+        })
+    }
+}
@@ -0,0 +1,27 @@
+//! Code upgrades. The wallet can only swap its own wasm through an executed
+//! `Upgrade` proposal, which needs the full threshold like any other
+//! configuration change. Storage carries over untouched.
+
+use soroban_sdk::{contractimpl, symbol_short, Address, BytesN, Env};
+
+use crate::{
+    ConfigChange, ExtendedChange, MultisigContract, MultisigContractArgs, MultisigContractClient, MultisigError,
+};
+
+impl MultisigContract {
+    /// Replaces the wallet's code with the uploaded wasm `new_wasm_hash`
+    /// once the current invocation returns.
+    pub(crate) fn upgrade(env: &Env, new_wasm_hash: BytesN<32>) {
+        env.deployer().update_current_contract_wasm(new_wasm_hash.clone());
+        Self::publish_event(env, (symbol_short!("upgraded"),), new_wasm_hash);
+    }
+}
+
+#[contractimpl]
+impl MultisigContract {
+    /// Proposes running the wallet on the uploaded wasm `new_wasm_hash`.
+    pub fn propose_upgrade(env: Env, caller: Address, new_wasm_hash: BytesN<32>) -> Result<u32, MultisigError> {
+        let change = ExtendedChange::Upgrade(new_wasm_hash);
+        Self::submit_config_change(env, caller, ConfigChange::Extended(change))
+    }
+}